@@ -0,0 +1,112 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static TOTAL_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_allocation(size: usize) {
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+        TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_deallocation(size: usize) {
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            CountingAllocator::record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            CountingAllocator::record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CountingAllocator::record_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CountingAllocator::record_deallocation(layout.size());
+            CountingAllocator::record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, Clone, Copy)]
+pub struct AllocationStats {
+    pub peak_bytes: usize,
+    pub total_bytes: usize,
+    pub allocation_count: usize,
+}
+
+// The peak is measured above whatever was already live when the phase
+// started, so data kept from a previous phase doesn't count again.
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    let starting_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(starting_bytes, Ordering::Relaxed);
+    let starting_total_bytes = TOTAL_BYTES.load(Ordering::Relaxed);
+    let starting_allocation_count = ALLOCATION_COUNT.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocationStats {
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed) - starting_bytes,
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed) - starting_total_bytes,
+        allocation_count: ALLOCATION_COUNT.load(Ordering::Relaxed) - starting_allocation_count,
+    };
+    (result, stats)
+}
+
+pub fn measure<T>(phase: &str, f: impl FnOnce() -> T) -> T {
+    if !cfg!(feature = "alloc-stats") {
+        return f();
+    }
+    let (result, stats) = track(f);
+    eprintln!(
+        "[alloc] {}: peak {}, total {} in {} allocations",
+        phase,
+        format_bytes(stats.peak_bytes),
+        format_bytes(stats.total_bytes),
+        stats.allocation_count
+    );
+    result
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} {}", bytes, units[0]),
+        _ => format!("{:.1} {}", value, units[unit]),
+    }
+}
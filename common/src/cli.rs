@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+
+pub struct Args {
+    positionals: Vec<String>,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl Args {
+    pub fn parse(options_with_values: &[&str]) -> Args {
+        Args::from(env::args().skip(1).collect(), options_with_values)
+    }
+
+    // Names listed in `options_with_values` take the next argument as their
+    // value. Any other argument starting with "--" is a flag.
+    pub fn from(args: Vec<String>, options_with_values: &[&str]) -> Args {
+        let mut positionals = Vec::new();
+        let mut options = HashMap::new();
+        let mut flags = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if options_with_values.contains(&arg.as_str()) {
+                let value = args.next().unwrap_or_default();
+                options.insert(arg, value);
+            } else if arg.starts_with("--") {
+                flags.push(arg);
+            } else {
+                positionals.push(arg);
+            }
+        }
+
+        Args {
+            positionals,
+            options,
+            flags,
+        }
+    }
+
+    pub fn get_input_file_path(&self, default_file_path: &str) -> String {
+        self.positionals
+            .first()
+            .cloned()
+            .unwrap_or(default_file_path.to_string())
+    }
+
+    pub fn get_positionals(&self) -> Vec<&str> {
+        self.positionals.iter().map(String::as_str).collect()
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    pub fn get_option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn parse_option<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.get_option(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("Invalid value for {}: {}", name, value)),
+            None => Ok(default),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+
+pub fn read_lines(file_path: &str) -> Vec<String> {
+    fs::read_to_string(file_path)
+        .expect("Couldn't read input file")
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+// Streams lines without holding the whole file, replacing invalid UTF-8
+// instead of failing on it. Read errors are passed on to the caller.
+// `bytes_read` counts the raw bytes, line endings included.
+pub struct LineReader<R> {
+    reader: R,
+    pub lines_read: u64,
+    pub bytes_read: u64,
+}
+
+impl LineReader<BufReader<File>> {
+    pub fn open(file_path: &str) -> io::Result<LineReader<BufReader<File>>> {
+        Ok(LineReader::new(BufReader::new(File::open(file_path)?)))
+    }
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            lines_read: 0,
+            bytes_read: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for LineReader<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        let mut line = Vec::new();
+        let count = match self.reader.read_until(b'\n', &mut line) {
+            Ok(count) => count,
+            Err(err) => return Some(Err(err)),
+        };
+        if count == 0 {
+            return None;
+        }
+        self.lines_read += 1;
+        self.bytes_read += count as u64;
+        if line.ends_with(b"\n") {
+            line.pop();
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+        }
+        Some(Ok(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
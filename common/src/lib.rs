@@ -0,0 +1,6 @@
+pub mod alloc_counter;
+pub mod cli;
+pub mod input;
+pub mod json;
+pub mod number_theory;
+pub mod thread_pool;
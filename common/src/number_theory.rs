@@ -0,0 +1,162 @@
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// None when there are no values or when the lcm doesn't fit in a u64.
+pub fn checked_lcm_of(values: impl IntoIterator<Item = u64>) -> Option<u64> {
+    let mut values = values.into_iter();
+    let first = values.next()?;
+    values.try_fold(first, checked_lcm)
+}
+
+// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b).
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        return (-old_r, -old_x, -old_y);
+    }
+    (old_r, old_x, old_y)
+}
+
+// Solves x ≡ residue (mod modulus) for every pair, without requiring the
+// moduli to be pairwise coprime. Returns the smallest non-negative solution
+// together with the combined modulus, or None when the congruences
+// contradict each other or the combined modulus overflows.
+pub fn chinese_remainder(congruences: &[(u64, u64)]) -> Option<(u128, u128)> {
+    let mut result: u128 = 0;
+    let mut result_modulus: u128 = 1;
+    for &(residue, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+        let modulus = modulus as u128;
+        let residue = residue as u128 % modulus;
+
+        let (g, inverse, _) = extended_gcd((result_modulus % modulus) as i128, modulus as i128);
+        let g = g as u128;
+        let difference = (residue + modulus - result % modulus) % modulus;
+        if !difference.is_multiple_of(g) {
+            return None;
+        }
+
+        let reduced_modulus = modulus / g;
+        let inverse = inverse.rem_euclid(reduced_modulus as i128) as u128;
+        // Both factors are below 2^64, so the product fits in a u128.
+        let step = (difference / g) * inverse % reduced_modulus;
+
+        result = result.checked_add(result_modulus.checked_mul(step)?)?;
+        result_modulus = result_modulus.checked_mul(reduced_modulus)?;
+        result %= result_modulus;
+    }
+    Some((result, result_modulus))
+}
+
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+pub fn isqrt_u64(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solves(solution: u128, congruences: &[(u64, u64)]) -> bool {
+        congruences.iter().all(|&(residue, modulus)| {
+            solution % modulus as u128 == residue as u128 % modulus as u128
+        })
+    }
+
+    #[test]
+    fn chinese_remainder_with_coprime_moduli() {
+        let congruences = [(2, 3), (3, 5), (2, 7)];
+        assert_eq!(chinese_remainder(&congruences), Some((23, 105)));
+    }
+
+    #[test]
+    fn chinese_remainder_with_shared_factors() {
+        let congruences = [(3, 4), (1, 6)];
+        assert_eq!(chinese_remainder(&congruences), Some((7, 12)));
+        let congruences = [(5, 12), (11, 18), (2, 3)];
+        assert_eq!(chinese_remainder(&congruences), Some((29, 36)));
+    }
+
+    #[test]
+    fn chinese_remainder_rejects_contradictions() {
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder(&[(1, 0)]), None);
+    }
+
+    #[test]
+    fn chinese_remainder_near_u64_max() {
+        let congruences = [(5, u64::MAX), (3, u64::MAX - 1)];
+        let (solution, modulus) = chinese_remainder(&congruences).unwrap();
+        assert_eq!(modulus, u64::MAX as u128 * (u64::MAX - 1) as u128);
+        assert!(solution < modulus);
+        assert!(solves(solution, &congruences));
+
+        let congruences = [(5, u64::MAX), (3, u64::MAX - 1), (1, u64::MAX - 2)];
+        assert_eq!(chinese_remainder(&congruences), None);
+    }
+
+    #[test]
+    fn isqrt_around_perfect_squares() {
+        assert_eq!(isqrt_u128(0), 0);
+        assert_eq!(isqrt_u128(1), 1);
+        for root in [2u128, 3, 10, 1 << 32, u32::MAX as u128, u64::MAX as u128] {
+            assert_eq!(isqrt_u128(root * root), root);
+            assert_eq!(isqrt_u128(root * root - 1), root - 1);
+            assert_eq!(isqrt_u128(root * root + 1), root);
+        }
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt_u64(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn checked_lcm_of_values() {
+        assert_eq!(checked_lcm_of([4, 6, 10]), Some(60));
+        assert_eq!(checked_lcm_of([7]), Some(7));
+        assert_eq!(checked_lcm_of(std::iter::empty()), None);
+        assert_eq!(checked_lcm_of([u64::MAX, u64::MAX - 1]), None);
+        assert_eq!(checked_lcm_of([u64::MAX, 0]), Some(0));
+    }
+
+    #[test]
+    fn extended_gcd_with_negative_inputs() {
+        for (a, b, expected) in [(-12, 18, 6), (240, -46, 2), (-7, -3, 1), (0, -5, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, expected);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+}
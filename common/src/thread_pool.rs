@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+const CHUNKS_PER_THREAD: usize = 4;
+
+pub fn get_thread_count(requested_threads: usize) -> usize {
+    match requested_threads {
+        0 => thread::available_parallelism()
+            .map(|threads| threads.get())
+            .unwrap_or(1),
+        _ => requested_threads,
+    }
+}
+
+// Items are split into chunks that idle workers pick up in turn, so a few
+// slow items don't leave the other threads waiting. Partial results are
+// reduced in chunk order, which keeps the answer identical to the serial
+// fold for any associative `reduce`.
+pub fn par_map_reduce<T, R>(
+    items: &[T],
+    threads: usize,
+    map: impl Fn(&T) -> R + Sync,
+    identity: R,
+    reduce: impl Fn(R, R) -> R + Sync,
+) -> R
+where
+    T: Sync,
+    R: Clone + Send,
+{
+    let threads = get_thread_count(threads).min(items.len());
+    if threads <= 1 {
+        return items
+            .iter()
+            .fold(identity, |acc, item| reduce(acc, map(item)));
+    }
+
+    let chunk_size = items.len().div_ceil(threads * CHUNKS_PER_THREAD);
+    let chunks: Vec<&[T]> = items.chunks(chunk_size).collect();
+    let next_chunk = AtomicUsize::new(0);
+    let partial_results: Mutex<Vec<Option<R>>> = Mutex::new(vec![None; chunks.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let identity = identity.clone();
+            let (map, reduce) = (&map, &reduce);
+            let (chunks, next_chunk, partial_results) = (&chunks, &next_chunk, &partial_results);
+            scope.spawn(move || loop {
+                let chunk_index = next_chunk.fetch_add(1, Ordering::Relaxed);
+                let Some(chunk) = chunks.get(chunk_index) else {
+                    break;
+                };
+                let partial_result = chunk
+                    .iter()
+                    .fold(identity.clone(), |acc, item| reduce(acc, map(item)));
+                partial_results.lock().unwrap()[chunk_index] = Some(partial_result);
+            });
+        }
+    });
+
+    partial_results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .fold(identity, &reduce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Appending isn't commutative, so any reordering of chunks shows up.
+    fn collect_squares(items: &[u64], threads: usize) -> Vec<u64> {
+        par_map_reduce(
+            items,
+            threads,
+            |item| vec![item * item],
+            Vec::new(),
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+        )
+    }
+
+    fn collect_squares_serially(items: &[u64]) -> Vec<u64> {
+        items.iter().map(|item| item * item).collect()
+    }
+
+    #[test]
+    fn matches_the_serial_fold_for_any_thread_count() {
+        let items: Vec<u64> = (0..1000).collect();
+        for threads in [0, 1, 2, 3, 7, 16] {
+            assert_eq!(
+                collect_squares(&items, threads),
+                collect_squares_serially(&items)
+            );
+        }
+    }
+
+    #[test]
+    fn more_threads_than_items() {
+        let items = [3, 1, 2];
+        assert_eq!(collect_squares(&items, 8), vec![9, 1, 4]);
+    }
+
+    #[test]
+    fn empty_items_give_the_identity() {
+        for threads in [0, 1, 4] {
+            assert!(collect_squares(&[], threads).is_empty());
+            assert_eq!(
+                par_map_reduce(&[] as &[u64], threads, |&item| item, 5, |a, b| a + b),
+                5
+            );
+        }
+    }
+
+    #[test]
+    fn sums_match_the_serial_fold() {
+        let items: Vec<u64> = (1..=10_000).collect();
+        for threads in [0, 1, 5, 10_001] {
+            assert_eq!(
+                par_map_reduce(&items, threads, |&item| item, 0, |a, b| a + b),
+                50_005_000
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::scanner::{Kind, Match};
+
+const HUNDRED: u32 = 100;
+const THOUSAND: u32 = 1000;
+
+// Reads number words as English compounds: "twentythree" is 23 and
+// "onehundredtwelve" is 112. None when the words don't form a number, like
+// "twelvethree" or "hundredhundred".
+fn parse_compound(values: &[u32]) -> Option<u32> {
+    let mut total = 0;
+    let mut current = 0;
+    let mut previous: Option<u32> = None;
+    for &value in values {
+        let follows_multiplier = matches!(previous, None | Some(HUNDRED) | Some(THOUSAND));
+        let follows_tens = matches!(previous, Some(20..=90));
+        match value {
+            1..=9 if follows_multiplier || follows_tens => current += value,
+            10..=90 if follows_multiplier => current += value,
+            HUNDRED if current > 0 && current < HUNDRED => current *= HUNDRED,
+            HUNDRED if previous.is_none() => current = HUNDRED,
+            THOUSAND if total == 0 && current > 0 && current < THOUSAND => {
+                total = current * THOUSAND;
+                current = 0;
+            }
+            THOUSAND if previous.is_none() => total = THOUSAND,
+            _ => return None,
+        }
+        previous = Some(value);
+    }
+    Some(total + current)
+}
+
+// Runs of adjacent number words are merged into one match holding the whole
+// number, and the matches inside it, like "seven" in "seventeen", are
+// dropped. At every step the longest word that still forms a number wins.
+// Matches that only overlap a compound, like "two" in "eightwo", are kept.
+pub fn combine_compounds(matches: Vec<Match>) -> Vec<Match> {
+    let mut combined: Vec<Match> = Vec::new();
+    let mut covered_until = 0;
+    for (i, found) in matches.iter().enumerate() {
+        if found.position + found.length <= covered_until {
+            continue;
+        }
+        if found.kind != Kind::Word {
+            combined.push(found.clone());
+            continue;
+        }
+
+        let longest_at = |position: usize, values: &[u32]| {
+            matches
+                .iter()
+                .filter(|next| next.kind == Kind::Word && next.position == position)
+                .filter(|next| {
+                    let mut extended = values.to_vec();
+                    extended.push(next.value);
+                    parse_compound(&extended).is_some()
+                })
+                .max_by_key(|next| next.length)
+        };
+        let Some(first) = longest_at(found.position, &[]) else {
+            combined.push(matches[i].clone());
+            continue;
+        };
+
+        let mut values = vec![first.value];
+        let mut end = first.position + first.length;
+        while let Some(next) = longest_at(end, &values) {
+            values.push(next.value);
+            end = next.position + next.length;
+        }
+        combined.push(Match {
+            position: found.position,
+            length: end - found.position,
+            value: parse_compound(&values).expect("Only valid compounds are extended"),
+            kind: Kind::Word,
+        });
+        covered_until = end;
+    }
+    combined
+}
@@ -0,0 +1,132 @@
+use std::io;
+
+use common::json::Json;
+
+use crate::rule::CalibrationRule;
+use crate::scanner::{Kind, Match, Scanner};
+use crate::{get_calibration_value, get_digits, Mode};
+
+fn get_kind_name(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Numeral => "numeral",
+        Kind::Word => "word",
+    }
+}
+
+fn get_matched_text<'a>(line: &'a str, found: &Match) -> &'a str {
+    &line[found.position..found.position + found.length]
+}
+
+fn add_to_sum(sum: u64, value: u64) -> Result<u64, String> {
+    sum.checked_add(value)
+        .ok_or("Calibration total overflowed a u64".to_string())
+}
+
+fn read_line(line: io::Result<String>, line_number: usize) -> Result<String, String> {
+    line.map_err(|err| format!("Couldn't read line {}: {}", line_number, err))
+}
+
+fn format_digit(digit: Option<&u32>) -> String {
+    digit
+        .map(|digit| digit.to_string())
+        .unwrap_or("-".to_string())
+}
+
+fn get_digit_or_null(digit: Option<&u32>) -> Json {
+    digit.map(Json::number).unwrap_or(Json::Null)
+}
+
+// Positions are byte offsets, the same ones the scanner works with.
+pub fn print_table(
+    lines: impl Iterator<Item = io::Result<String>>,
+    scanner: &Scanner,
+    mode: Mode,
+    rule: CalibrationRule,
+) -> Result<(), String> {
+    println!(
+        "{:>5}  {:>5}  {:>5}  {:>4}  {:<50}  Text",
+        "Line", "Value", "First", "Last", "Matches"
+    );
+    let mut sum: u64 = 0;
+    for (i, line) in lines.enumerate() {
+        let line = read_line(line, i + 1)?;
+        let matches = scanner.find_matches(&line);
+        let digits = get_digits(&matches, mode);
+        let value = get_calibration_value(&matches, mode, rule).unwrap_or(0);
+        sum = add_to_sum(sum, value)?;
+
+        let found_numbers: Vec<String> = matches
+            .iter()
+            .map(|found| match found.kind {
+                Kind::Numeral => format!("{}@{} numeral", found.value, found.position),
+                Kind::Word => format!(
+                    "{}@{} word({})",
+                    found.value,
+                    found.position,
+                    get_matched_text(&line, found)
+                ),
+            })
+            .collect();
+        println!(
+            "{:>5}  {:>5}  {:>5}  {:>4}  {:<50}  {}",
+            i + 1,
+            value,
+            format_digit(digits.first()),
+            format_digit(digits.last()),
+            found_numbers.join(" "),
+            line
+        );
+    }
+    println!("Sum of calibration values: {}", sum);
+    Ok(())
+}
+
+fn get_match_json(line: &str, found: &Match) -> Json {
+    Json::object(vec![
+        ("position", Json::number(found.position)),
+        ("value", Json::number(found.value)),
+        ("kind", Json::string(get_kind_name(found.kind))),
+        ("text", Json::string(get_matched_text(line, found))),
+    ])
+}
+
+// Printed line by line, so a large document doesn't have to fit in memory.
+pub fn print_json(
+    lines: impl Iterator<Item = io::Result<String>>,
+    scanner: &Scanner,
+    mode: Mode,
+    rule: CalibrationRule,
+) -> Result<(), String> {
+    let mut sum: u64 = 0;
+    print!("{{\"lines\":[");
+    for (i, line) in lines.enumerate() {
+        let line = read_line(line, i + 1)?;
+        let matches = scanner.find_matches(&line);
+        let digits = get_digits(&matches, mode);
+        let value = get_calibration_value(&matches, mode, rule).unwrap_or(0);
+        sum = add_to_sum(sum, value)?;
+
+        let explanation = Json::object(vec![
+            ("line", Json::number(i + 1)),
+            ("text", Json::string(&line)),
+            (
+                "matches",
+                Json::Array(
+                    matches
+                        .iter()
+                        .map(|found| get_match_json(&line, found))
+                        .collect(),
+                ),
+            ),
+            ("first", get_digit_or_null(digits.first())),
+            ("last", get_digit_or_null(digits.last())),
+            ("value", Json::number(value)),
+        ]);
+        if i > 0 {
+            println!(",");
+        }
+        print!("{}", explanation);
+    }
+    println!("],\"sum\":{}}}", sum);
+    Ok(())
+}
@@ -1,76 +1,452 @@
-use std::fs;
+use std::io;
+use std::process;
+use std::time::Instant;
+
+use common::alloc_counter;
+use common::cli::Args;
+use common::input::LineReader;
+use common::thread_pool;
+
+mod compound;
+mod explain;
+mod rule;
+mod scanner;
+mod vocabulary;
 
 const INPUT_FILEPATH: &str = "input.txt";
+// Non-ASCII lines, which add up to 121 and 324.
+// const INPUT_FILEPATH: &str = "utf8_input.txt";
+// Overlapping and mixed-case words. With --mode both, all overlaps add up to
+// 233, or 321 with --ignore-case, and both other policies to 231 and 320.
+// const INPUT_FILEPATH: &str = "overlap_input.txt";
+// Compound numbers, adding up to 199 with --mode both --compounds.
+// const INPUT_FILEPATH: &str = "compound_input.txt";
+const DEFAULT_LANGUAGES: &str = "en";
+const BATCH_LINES: usize = 1 << 16;
 
-fn read_input() -> Vec<String> {
-    fs::read_to_string(INPUT_FILEPATH)
-        .expect("Couldn't read input file")
-        .lines()
-        .map(String::from)
-        .collect()
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    Digits,
+    Words,
+    Both,
 }
 
-fn get_digits_and_their_indices(line: &str) -> Vec<[u32; 2]> {
-    let mut digits_and_their_indices = Vec::new();
-    for (i, ch) in line.chars().enumerate() {
-        if ch.is_digit(10) {
-            digits_and_their_indices.push([i as u32, ch.to_digit(10).unwrap()]);
+impl Mode {
+    fn from(text: &str) -> Result<Mode, String> {
+        match text {
+            "digits" => Ok(Mode::Digits),
+            "words" => Ok(Mode::Words),
+            "both" => Ok(Mode::Both),
+            _ => Err(format!("Unknown mode: {}", text)),
+        }
+    }
+
+    fn accepts(&self, kind: scanner::Kind) -> bool {
+        match self {
+            Mode::Digits => kind == scanner::Kind::Numeral,
+            Mode::Words => kind == scanner::Kind::Word,
+            Mode::Both => true,
         }
     }
-    digits_and_their_indices
 }
 
-fn get_word_spelled_digits_and_their_indices(line: &str) -> Vec<[u32; 2]> {
-    let word_spelled_numbers = [
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
-    let mut words_found_and_their_indices: Vec<(usize, &str)> = Vec::new();
-    for word in word_spelled_numbers {
-        let mut matches = line.match_indices(word).collect();
-        words_found_and_their_indices.append(&mut matches);
-    }
-
-    let mut digits_and_their_indices: Vec<[u32; 2]> = Vec::new();
-    for (index, word) in words_found_and_their_indices {
-        for (digit, w) in word_spelled_numbers.iter().enumerate() {
-            if w.contains(word) {
-                digits_and_their_indices.push([index as u32, digit as u32 + 1]);
-            }
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MissingDigits {
+    // Lines without digits add nothing to the total.
+    Ignore,
+    // They add nothing either, but are listed after the totals.
+    Lenient,
+    // The first one stops the run.
+    Strict,
+}
+
+impl MissingDigits {
+    fn from(text: &str) -> Result<MissingDigits, String> {
+        match text {
+            "ignore" => Ok(MissingDigits::Ignore),
+            "lenient" => Ok(MissingDigits::Lenient),
+            "strict" => Ok(MissingDigits::Strict),
+            _ => Err(format!("Unknown policy for lines without digits: {}", text)),
         }
     }
-    digits_and_their_indices
 }
 
-fn merge_vectors(vec1: Vec<[u32; 2]>, vec2: Vec<[u32; 2]>) -> Vec<[u32; 2]> {
-    let merged_vec = vec1
+#[derive(Clone, Debug, Default)]
+struct Total {
+    sum: u64,
+    // (line number, text) of every line without digits, in input order.
+    lines_without_digits: Vec<(u64, String)>,
+}
+
+fn add_totals(a: Vec<Total>, b: Vec<Total>) -> Result<Vec<Total>, String> {
+    a.into_iter()
+        .zip(b)
+        .map(|(a, b)| {
+            let mut lines_without_digits = a.lines_without_digits;
+            lines_without_digits.extend(b.lines_without_digits);
+            Ok(Total {
+                sum: a
+                    .sum
+                    .checked_add(b.sum)
+                    .ok_or("Calibration total overflowed a u64")?,
+                lines_without_digits,
+            })
+        })
+        .collect()
+}
+
+fn get_vocabulary(
+    languages: &str,
+    vocabulary_files: Option<&str>,
+    combine_compounds: bool,
+    include_zero: bool,
+) -> Result<vocabulary::Vocabulary, String> {
+    let mut vocabulary = vocabulary::Vocabulary::new();
+    if combine_compounds {
+        vocabulary.add_compound_words()?;
+    }
+    for language in languages.split(',').filter(|language| !language.is_empty()) {
+        vocabulary.add_language(language.trim(), include_zero)?;
+    }
+    for file_path in vocabulary_files
+        .into_iter()
+        .flat_map(|files| files.split(','))
+    {
+        vocabulary.add_file(file_path.trim())?;
+    }
+    Ok(vocabulary)
+}
+
+// A compound number contributes all of its digits, so "twelve" counts as a
+// 1 when it comes first and as a 2 when it comes last.
+fn get_digits(matches: &[scanner::Match], mode: Mode) -> Vec<u32> {
+    matches
         .iter()
-        .chain(vec2.iter())
-        .cloned()
-        .collect();
-    merged_vec
+        .filter(|found| mode.accepts(found.kind))
+        .flat_map(|found| {
+            found
+                .value
+                .to_string()
+                .chars()
+                .filter_map(|ch| ch.to_digit(10))
+                .collect::<Vec<u32>>()
+        })
+        .collect()
+}
+
+fn get_calibration_value(
+    matches: &[scanner::Match],
+    mode: Mode,
+    rule: rule::CalibrationRule,
+) -> Option<u64> {
+    rule.apply(&get_digits(matches, mode))
+}
+
+// Every line is scanned once, and each mode picks its digits from the same
+// matches. Lines are read in batches, so memory use doesn't grow with the
+// size of the document.
+fn sum_calibration_values(
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+    scanner: &scanner::Scanner,
+    modes: &[Mode],
+    rule: rule::CalibrationRule,
+    missing_digits: MissingDigits,
+    threads: usize,
+) -> Result<Vec<Total>, String> {
+    let mut totals = vec![Total::default(); modes.len()];
+    let mut line_number = 0;
+    loop {
+        let batch: Vec<(u64, String)> = lines
+            .by_ref()
+            .take(BATCH_LINES)
+            .map(|line| {
+                line_number += 1;
+                line.map(|line| (line_number, line))
+            })
+            .collect::<io::Result<Vec<(u64, String)>>>()
+            .map_err(|err| format!("Couldn't read line {}: {}", line_number, err))?;
+        if batch.is_empty() {
+            return Ok(totals);
+        }
+        let batch_totals = thread_pool::par_map_reduce(
+            &batch,
+            threads,
+            |(line_number, line)| {
+                let matches = scanner.find_matches(line);
+                Ok(modes
+                    .iter()
+                    .map(|mode| match get_calibration_value(&matches, *mode, rule) {
+                        Some(value) => Total {
+                            sum: value,
+                            lines_without_digits: Vec::new(),
+                        },
+                        None if missing_digits == MissingDigits::Ignore => Total::default(),
+                        None => Total {
+                            sum: 0,
+                            lines_without_digits: vec![(*line_number, line.clone())],
+                        },
+                    })
+                    .collect::<Vec<Total>>())
+            },
+            Ok(vec![Total::default(); modes.len()]),
+            |a, b| add_totals(a?, b?),
+        )?;
+        totals = add_totals(totals, batch_totals)?;
+        if missing_digits == MissingDigits::Strict {
+            // Modes see the same lines, so the earliest one is the first of any mode.
+            if let Some((line_number, line)) = totals
+                .iter()
+                .filter_map(|total| total.lines_without_digits.first())
+                .min_by_key(|(line_number, _)| *line_number)
+            {
+                return Err(format!("Line {} has no digits: {:?}", line_number, line));
+            }
+        }
+    }
+}
+
+// A rejected document exits with code 1 rather than panicking, so the
+// runner's robustness check doesn't count it as a crash.
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn print_lines_without_digits(label: &str, total: &Total) {
+    if total.lines_without_digits.is_empty() {
+        return;
+    }
+    println!(
+        "{} line(s) without digits in {}:",
+        total.lines_without_digits.len(),
+        label
+    );
+    for (line_number, line) in &total.lines_without_digits {
+        println!("    line {}: {:?}", line_number, line);
+    }
+}
+
+fn print_throughput(lines: &LineReader<impl std::io::BufRead>, before: Instant) {
+    let elapsed = before.elapsed();
+    let mebibytes = lines.bytes_read as f64 / (1024.0 * 1024.0);
+    println!(
+        "Processed {} lines ({:.2} MiB) in {:.2?}, {:.1} MiB/s",
+        lines.lines_read,
+        mebibytes,
+        elapsed,
+        mebibytes / elapsed.as_secs_f64()
+    );
 }
 
 fn main() {
-    let lines = read_input();
-    let mut sum = 0;
-    for line in lines {
-        let digits_and_their_indices = get_digits_and_their_indices(&line);
-        let word_spelled_digits_and_their_indices =
-            get_word_spelled_digits_and_their_indices(&line);
-        
-        let mut digits = merge_vectors(
-            digits_and_their_indices,
-            word_spelled_digits_and_their_indices,
-        );
-        
-        digits.sort_by_key(|k| k[0]);
-
-        if digits.len() > 0 {
-            let first: String = digits.first().unwrap()[1].to_string();
-            let last: String = digits.last().unwrap()[1].to_string();
-            let value = first + &last;
-            sum += value.parse::<u32>().unwrap();
+    let args = Args::parse(&[
+        "--threads",
+        "--lang",
+        "--vocabulary",
+        "--mode",
+        "--format",
+        "--rule",
+        "--overlaps",
+        "--missing-digits",
+    ]);
+    let threads = args
+        .parse_option("--threads", 1)
+        .expect("Invalid number of threads");
+    let mut lines = LineReader::open(&args.get_input_file_path(INPUT_FILEPATH))
+        .expect("Couldn't read input file");
+    let vocabulary = get_vocabulary(
+        args.get_option("--lang").unwrap_or(DEFAULT_LANGUAGES),
+        args.get_option("--vocabulary"),
+        args.has_flag("--compounds"),
+        args.has_flag("--zero"),
+    )
+    .expect("Invalid vocabulary");
+    let overlap_policy =
+        scanner::OverlapPolicy::from(args.get_option("--overlaps").unwrap_or("all"))
+            .expect("Invalid overlap policy");
+    let scanner = alloc_counter::measure("scanner", || {
+        scanner::Scanner::new(
+            &vocabulary.get_patterns(),
+            scanner::Options {
+                ignore_case: args.has_flag("--ignore-case"),
+                overlap_policy,
+                combine_compounds: args.has_flag("--compounds"),
+            },
+        )
+    });
+    let rule = rule::CalibrationRule::from(args.get_option("--rule").unwrap_or("first-last"))
+        .expect("Invalid calibration rule");
+    let missing_digits =
+        MissingDigits::from(args.get_option("--missing-digits").unwrap_or("ignore"))
+            .expect("Invalid policy for lines without digits");
+
+    if args.has_flag("--explain") {
+        let mode = Mode::from(args.get_option("--mode").unwrap_or("both")).expect("Invalid mode");
+        match args.get_option("--format").unwrap_or("table") {
+            "table" => explain::print_table(lines, &scanner, mode, rule),
+            "json" => explain::print_json(lines, &scanner, mode, rule),
+            format => Err(format!("Unknown explain format: {}", format)),
+        }
+        .unwrap_or_else(|err| exit_with_error(&err));
+        return;
+    }
+
+    let before = Instant::now();
+    match args.get_option("--mode") {
+        Some(mode) => {
+            let mode = Mode::from(mode).expect("Invalid mode");
+            let totals = alloc_counter::measure("calibration values", || {
+                sum_calibration_values(&mut lines, &scanner, &[mode], rule, missing_digits, threads)
+            })
+            .unwrap_or_else(|err| exit_with_error(&err));
+            println!("Sum of calibration values: {}", totals[0].sum);
+            print_lines_without_digits("the calibration values", &totals[0]);
         }
+        None => {
+            // Lines are read once for both parts, so they share one measurement.
+            let totals = alloc_counter::measure("both parts", || {
+                sum_calibration_values(
+                    &mut lines,
+                    &scanner,
+                    &[Mode::Digits, Mode::Both],
+                    rule,
+                    missing_digits,
+                    threads,
+                )
+            })
+            .unwrap_or_else(|err| exit_with_error(&err));
+            println!("Part one solution: {}", totals[0].sum);
+            println!("Part two solution: {}", totals[1].sum);
+            print_lines_without_digits("part one", &totals[0]);
+            print_lines_without_digits("part two", &totals[1]);
+        }
+    }
+    print_throughput(&lines, before);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_scanner(ignore_case: bool) -> scanner::Scanner {
+        let vocabulary = get_vocabulary("en", None, false, false).unwrap();
+        scanner::Scanner::new(
+            &vocabulary.get_patterns(),
+            scanner::Options {
+                ignore_case,
+                overlap_policy: scanner::OverlapPolicy::AllowOverlaps,
+                combine_compounds: false,
+            },
+        )
+    }
+
+    // (byte position, value) of every match, then the first/last value for
+    // digits only and for digits and words.
+    fn explain(scanner: &scanner::Scanner, line: &str) -> (Vec<(usize, u32)>, u64, u64) {
+        let matches = scanner.find_matches(line);
+        let positions = matches
+            .iter()
+            .map(|found| (found.position, found.value))
+            .collect();
+        let value = |mode| {
+            get_calibration_value(&matches, mode, rule::CalibrationRule::FirstAndLast).unwrap_or(0)
+        };
+        (positions, value(Mode::Digits), value(Mode::Both))
+    }
+
+    #[test]
+    fn zero_is_only_a_word_with_the_flag() {
+        let scanner = get_scanner(false);
+        assert_eq!(
+            explain(&scanner, "zero5nine"),
+            (vec![(4, 5), (5, 9)], 55, 59)
+        );
+
+        let vocabulary = get_vocabulary("en,es", None, false, true).unwrap();
+        let scanner = scanner::Scanner::new(
+            &vocabulary.get_patterns(),
+            scanner::Options {
+                ignore_case: false,
+                overlap_policy: scanner::OverlapPolicy::AllowOverlaps,
+                combine_compounds: false,
+            },
+        );
+        assert_eq!(
+            explain(&scanner, "zero5nine"),
+            (vec![(0, 0), (4, 5), (5, 9)], 55, 9)
+        );
+        assert_eq!(explain(&scanner, "cero7"), (vec![(0, 0), (4, 7)], 77, 7));
+    }
+
+    #[test]
+    fn emoji_lines_use_byte_positions() {
+        let scanner = get_scanner(false);
+        assert_eq!(
+            explain(&scanner, "🎄🎄one2"),
+            (vec![(8, 1), (11, 2)], 22, 12)
+        );
+        assert_eq!(
+            explain(&scanner, "two🎄🎄🎄3"),
+            (vec![(0, 2), (15, 3)], 33, 23)
+        );
+        assert_eq!(
+            explain(&scanner, "çãoeight🎄seven"),
+            (vec![(5, 8), (14, 7)], 0, 87)
+        );
+    }
+
+    #[test]
+    fn accented_lines_use_byte_positions() {
+        let scanner = get_scanner(false);
+        assert_eq!(
+            explain(&scanner, "ééénine1x"),
+            (vec![(6, 9), (10, 1)], 11, 91)
+        );
+        assert_eq!(explain(&scanner, "caféfive"), (vec![(5, 5)], 0, 55));
+        assert_eq!(explain(&scanner, "5ñsix"), (vec![(0, 5), (3, 6)], 55, 56));
+    }
+
+    #[test]
+    fn case_folding_keeps_byte_positions() {
+        let scanner = get_scanner(true);
+        assert_eq!(
+            explain(&scanner, "ÉÉÉNINE1x"),
+            (vec![(6, 9), (10, 1)], 11, 91)
+        );
+        assert_eq!(explain(&scanner, "🎄Seven"), (vec![(4, 7)], 0, 77));
+    }
+
+    #[test]
+    fn read_errors_are_returned() {
+        let mut lines = vec![
+            Ok("1abc2".to_string()),
+            Err(io::Error::other("disk went away")),
+        ]
+        .into_iter();
+        let result = sum_calibration_values(
+            &mut lines,
+            &get_scanner(false),
+            &[Mode::Digits],
+            rule::CalibrationRule::FirstAndLast,
+            MissingDigits::Ignore,
+            1,
+        );
+        assert_eq!(
+            result.map(|totals| totals[0].sum),
+            Err("Couldn't read line 2: disk went away".to_string())
+        );
+    }
+
+    #[test]
+    fn overflowing_totals_are_returned() {
+        let total = |sum| Total {
+            sum,
+            lines_without_digits: Vec::new(),
+        };
+        assert!(add_totals(vec![total(u64::MAX)], vec![total(1)]).is_err());
+        assert_eq!(
+            add_totals(vec![total(u64::MAX - 1)], vec![total(1)]).map(|totals| totals[0].sum),
+            Ok(u64::MAX)
+        );
     }
-    println!("{}", sum);
 }
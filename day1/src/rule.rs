@@ -0,0 +1,59 @@
+// Longest run of digits whose concatenation always fits in a u64.
+const MAX_CONCATENATED_DIGITS: usize = 19;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CalibrationRule {
+    // The first digit followed by the last one, the puzzle's own rule.
+    FirstAndLast,
+    SumOfAll,
+    // The first N digits concatenated, or all of them when there are fewer.
+    FirstN(usize),
+    // The largest digit followed by the smallest one.
+    LargestAndSmallest,
+}
+
+impl CalibrationRule {
+    pub fn from(text: &str) -> Result<CalibrationRule, String> {
+        match text {
+            "first-last" => Ok(CalibrationRule::FirstAndLast),
+            "sum" => Ok(CalibrationRule::SumOfAll),
+            "largest-smallest" => Ok(CalibrationRule::LargestAndSmallest),
+            _ => {
+                let count = text
+                    .strip_prefix("first:")
+                    .and_then(|count| count.parse().ok())
+                    .ok_or(format!("Unknown calibration rule: {}", text))?;
+                if count == 0 || count > MAX_CONCATENATED_DIGITS {
+                    return Err(format!(
+                        "first:N needs N between 1 and {}",
+                        MAX_CONCATENATED_DIGITS
+                    ));
+                }
+                Ok(CalibrationRule::FirstN(count))
+            }
+        }
+    }
+
+    fn concatenate(digits: impl Iterator<Item = u32>) -> u64 {
+        digits.fold(0, |value, digit| value * 10 + digit as u64)
+    }
+
+    // None when the line has no digits at all.
+    pub fn apply(&self, digits: &[u32]) -> Option<u64> {
+        let (first, last) = (*digits.first()?, *digits.last()?);
+        Some(match self {
+            CalibrationRule::FirstAndLast => {
+                CalibrationRule::concatenate([first, last].into_iter())
+            }
+            CalibrationRule::SumOfAll => digits.iter().map(|&digit| digit as u64).sum(),
+            CalibrationRule::FirstN(count) => {
+                CalibrationRule::concatenate(digits.iter().take(*count).copied())
+            }
+            CalibrationRule::LargestAndSmallest => {
+                let largest = *digits.iter().max()?;
+                let smallest = *digits.iter().min()?;
+                CalibrationRule::concatenate([largest, smallest].into_iter())
+            }
+        })
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::VecDeque;
+
+use crate::compound;
+
+const ROOT: usize = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Numeral,
+    Word,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverlapPolicy {
+    // Every match is reported, so "twone" yields both 2 and 1.
+    AllowOverlaps,
+    // Among matches starting at the same place the longest one wins, and
+    // anything overlapping it is dropped: "twone" yields only 2.
+    LeftmostLongest,
+    // The match that ends first wins, and scanning resumes right after it.
+    NonOverlapping,
+}
+
+impl OverlapPolicy {
+    pub fn from(text: &str) -> Result<OverlapPolicy, String> {
+        match text {
+            "all" => Ok(OverlapPolicy::AllowOverlaps),
+            "leftmost-longest" => Ok(OverlapPolicy::LeftmostLongest),
+            "non-overlapping" => Ok(OverlapPolicy::NonOverlapping),
+            _ => Err(format!("Unknown overlap policy: {}", text)),
+        }
+    }
+}
+
+// Positions are byte offsets into the line. Patterns are valid UTF-8, so a
+// match always starts on a char boundary and `&line[position..]` is safe.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    pub position: usize,
+    pub length: usize,
+    pub value: u32,
+    pub kind: Kind,
+}
+
+#[derive(Clone, Debug)]
+struct State {
+    transitions: [usize; 256],
+    // (pattern length, value, kind) of every pattern ending here, including
+    // the ones reached through failure links.
+    outputs: Vec<(usize, u32, Kind)>,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            transitions: [ROOT; 256],
+            outputs: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub ignore_case: bool,
+    pub overlap_policy: OverlapPolicy,
+    pub combine_compounds: bool,
+}
+
+// Aho–Corasick automaton over bytes. The failure links are folded into the
+// transition table, so scanning a line takes one lookup per byte and
+// overlapping patterns such as "twone" or "eightwo" all get reported.
+#[derive(Clone, Debug)]
+pub struct Scanner {
+    states: Vec<State>,
+    options: Options,
+}
+
+// Lowercases only the characters whose lowercase form takes as many bytes,
+// which keeps every byte offset pointing at the same place in the original.
+fn fold_case(text: &str) -> String {
+    text.chars()
+        .map(|ch| {
+            let mut lowercase = ch.to_lowercase();
+            match (lowercase.next(), lowercase.next()) {
+                (Some(lower), None) if lower.len_utf8() == ch.len_utf8() => lower,
+                _ => ch,
+            }
+        })
+        .collect()
+}
+
+fn select_matches(matches: Vec<Match>, overlap_policy: OverlapPolicy) -> Vec<Match> {
+    let mut matches = matches;
+    match overlap_policy {
+        OverlapPolicy::AllowOverlaps => return matches,
+        OverlapPolicy::LeftmostLongest => {
+            matches.sort_by_key(|found| (found.position, usize::MAX - found.length))
+        }
+        OverlapPolicy::NonOverlapping => {
+            matches.sort_by_key(|found| (found.position + found.length, found.position))
+        }
+    }
+
+    let mut selected: Vec<Match> = Vec::new();
+    let mut next_free_position = 0;
+    for found in matches {
+        if found.position >= next_free_position {
+            next_free_position = found.position + found.length;
+            selected.push(found);
+        }
+    }
+    selected
+}
+
+impl Scanner {
+    pub fn new(patterns: &[(&str, u32, Kind)], options: Options) -> Scanner {
+        let mut states = vec![State::new()];
+        let mut has_transition = vec![[false; 256]];
+
+        for &(pattern, value, kind) in patterns {
+            let pattern = if options.ignore_case {
+                fold_case(pattern)
+            } else {
+                pattern.to_string()
+            };
+            let mut state = ROOT;
+            for &byte in pattern.as_bytes() {
+                if !has_transition[state][byte as usize] {
+                    states.push(State::new());
+                    has_transition.push([false; 256]);
+                    has_transition[state][byte as usize] = true;
+                    states[state].transitions[byte as usize] = states.len() - 1;
+                }
+                state = states[state].transitions[byte as usize];
+            }
+            let output = (pattern.len(), value, kind);
+            if !pattern.is_empty() && !states[state].outputs.contains(&output) {
+                states[state].outputs.push(output);
+            }
+        }
+
+        let mut failures = vec![ROOT; states.len()];
+        let mut queue: VecDeque<usize> = (0..256)
+            .filter(|&byte| has_transition[ROOT][byte])
+            .map(|byte| states[ROOT].transitions[byte])
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            let failure_outputs = states[failures[state]].outputs.clone();
+            states[state].outputs.extend(failure_outputs);
+            let children = has_transition[state];
+            for (byte, &has_child) in children.iter().enumerate() {
+                if has_child {
+                    let next = states[state].transitions[byte];
+                    failures[next] = states[failures[state]].transitions[byte];
+                    queue.push_back(next);
+                } else {
+                    states[state].transitions[byte] = states[failures[state]].transitions[byte];
+                }
+            }
+        }
+
+        Scanner { states, options }
+    }
+
+    // Matches are ordered by the byte offset they start at.
+    pub fn find_matches(&self, line: &str) -> Vec<Match> {
+        let folded_line;
+        let line = if self.options.ignore_case {
+            folded_line = fold_case(line);
+            &folded_line
+        } else {
+            line
+        };
+
+        let mut matches = Vec::new();
+        let mut state = ROOT;
+        for (i, &byte) in line.as_bytes().iter().enumerate() {
+            state = self.states[state].transitions[byte as usize];
+            for &(length, value, kind) in &self.states[state].outputs {
+                matches.push(Match {
+                    position: i + 1 - length,
+                    length,
+                    value,
+                    kind,
+                });
+            }
+        }
+        matches.sort_by_key(|found| found.position);
+        // Compounds are combined first, so the overlap policy can't drop
+        // "seventeen" in favour of the "seven" inside it.
+        if self.options.combine_compounds {
+            matches = compound::combine_compounds(matches);
+        }
+        select_matches(matches, self.options.overlap_policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary::Vocabulary;
+
+    fn get_scanner(languages: &[&str], options: Options) -> Scanner {
+        let mut vocabulary = Vocabulary::new();
+        if options.combine_compounds {
+            vocabulary.add_compound_words().unwrap();
+        }
+        for language in languages {
+            vocabulary.add_language(language, false).unwrap();
+        }
+        Scanner::new(&vocabulary.get_patterns(), options)
+    }
+
+    fn get_options(overlap_policy: OverlapPolicy) -> Options {
+        Options {
+            ignore_case: false,
+            overlap_policy,
+            combine_compounds: false,
+        }
+    }
+
+    // (position, value) of every match, which is what the rules look at.
+    fn find_values(scanner: &Scanner, line: &str) -> Vec<(usize, u32)> {
+        scanner
+            .find_matches(line)
+            .iter()
+            .map(|found| (found.position, found.value))
+            .collect()
+    }
+
+    fn find_spans(scanner: &Scanner, line: &str) -> Vec<(usize, usize, u32)> {
+        scanner
+            .find_matches(line)
+            .iter()
+            .map(|found| (found.position, found.length, found.value))
+            .collect()
+    }
+
+    #[test]
+    fn all_overlaps_are_reported() {
+        let scanner = get_scanner(&["en"], get_options(OverlapPolicy::AllowOverlaps));
+        assert_eq!(find_spans(&scanner, "twone"), [(0, 3, 2), (2, 3, 1)]);
+        assert_eq!(find_spans(&scanner, "eightwo"), [(0, 5, 8), (4, 3, 2)]);
+        assert_eq!(find_spans(&scanner, "sevenine"), [(0, 5, 7), (4, 4, 9)]);
+        assert_eq!(find_spans(&scanner, "a1b"), [(1, 1, 1)]);
+    }
+
+    #[test]
+    fn leftmost_longest_drops_overlaps() {
+        let scanner = get_scanner(&["en"], get_options(OverlapPolicy::LeftmostLongest));
+        assert_eq!(find_spans(&scanner, "twone"), [(0, 3, 2)]);
+        assert_eq!(find_spans(&scanner, "eightwo3"), [(0, 5, 8), (7, 1, 3)]);
+
+        let scanner = get_scanner(&["pt"], get_options(OverlapPolicy::LeftmostLongest));
+        assert_eq!(find_spans(&scanner, "uma"), [(0, 3, 1)]);
+    }
+
+    #[test]
+    fn non_overlapping_keeps_the_match_ending_first() {
+        let scanner = get_scanner(&["en"], get_options(OverlapPolicy::NonOverlapping));
+        assert_eq!(find_spans(&scanner, "twone"), [(0, 3, 2)]);
+        assert_eq!(find_spans(&scanner, "eightwo3"), [(0, 5, 8), (7, 1, 3)]);
+
+        let scanner = get_scanner(&["pt"], get_options(OverlapPolicy::NonOverlapping));
+        assert_eq!(find_spans(&scanner, "uma"), [(0, 2, 1)]);
+    }
+
+    #[test]
+    fn leftmost_longest_and_non_overlapping_differ_on_nested_words() {
+        let patterns = [("seven", 7, Kind::Word), ("seventeen", 17, Kind::Word)];
+        let find = |overlap_policy| {
+            let scanner = Scanner::new(&patterns, get_options(overlap_policy));
+            find_spans(&scanner, "seventeen")
+        };
+        assert_eq!(find(OverlapPolicy::AllowOverlaps), [(0, 5, 7), (0, 9, 17)]);
+        assert_eq!(find(OverlapPolicy::LeftmostLongest), [(0, 9, 17)]);
+        assert_eq!(find(OverlapPolicy::NonOverlapping), [(0, 5, 7)]);
+    }
+
+    #[test]
+    fn mixed_case_words_under_every_policy() {
+        let find = |overlap_policy, ignore_case, line| {
+            let scanner = get_scanner(
+                &["en"],
+                Options {
+                    ignore_case,
+                    ..get_options(overlap_policy)
+                },
+            );
+            find_spans(&scanner, line)
+        };
+        assert_eq!(
+            find(OverlapPolicy::AllowOverlaps, false, "xTwOne3"),
+            [(6, 1, 3)]
+        );
+        assert_eq!(
+            find(OverlapPolicy::AllowOverlaps, true, "xTwOne3"),
+            [(1, 3, 2), (3, 3, 1), (6, 1, 3)]
+        );
+        assert_eq!(
+            find(OverlapPolicy::LeftmostLongest, true, "xTwOne3"),
+            [(1, 3, 2), (6, 1, 3)]
+        );
+        assert_eq!(
+            find(OverlapPolicy::NonOverlapping, true, "xTwOne3"),
+            [(1, 3, 2), (6, 1, 3)]
+        );
+
+        assert_eq!(find(OverlapPolicy::AllowOverlaps, false, "NINEight"), []);
+        assert_eq!(
+            find(OverlapPolicy::AllowOverlaps, true, "NINEight"),
+            [(0, 4, 9), (3, 5, 8)]
+        );
+        assert_eq!(
+            find(OverlapPolicy::LeftmostLongest, true, "NINEight"),
+            [(0, 4, 9)]
+        );
+        assert_eq!(
+            find(OverlapPolicy::NonOverlapping, true, "NINEight"),
+            [(0, 4, 9)]
+        );
+    }
+
+    #[test]
+    fn compounds_survive_every_overlap_policy() {
+        for overlap_policy in [
+            OverlapPolicy::AllowOverlaps,
+            OverlapPolicy::LeftmostLongest,
+            OverlapPolicy::NonOverlapping,
+        ] {
+            let scanner = get_scanner(
+                &["en"],
+                Options {
+                    combine_compounds: true,
+                    ..get_options(overlap_policy)
+                },
+            );
+            assert_eq!(find_values(&scanner, "seventeen"), [(0, 17)]);
+            assert_eq!(find_values(&scanner, "xsixteenx"), [(1, 16)]);
+            assert_eq!(find_values(&scanner, "twentythree"), [(0, 23)]);
+            assert_eq!(
+                find_values(&scanner, "onehundredtwelve9"),
+                [(0, 112), (16, 9)]
+            );
+        }
+    }
+
+    #[test]
+    fn overlapping_compounds_depend_on_the_policy() {
+        let find = |overlap_policy| {
+            let scanner = get_scanner(
+                &["en"],
+                Options {
+                    combine_compounds: true,
+                    ..get_options(overlap_policy)
+                },
+            );
+            find_values(&scanner, "eightwo")
+        };
+        assert_eq!(find(OverlapPolicy::AllowOverlaps), [(0, 8), (4, 2)]);
+        assert_eq!(find(OverlapPolicy::LeftmostLongest), [(0, 8)]);
+        assert_eq!(find(OverlapPolicy::NonOverlapping), [(0, 8)]);
+    }
+}
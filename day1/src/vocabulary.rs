@@ -0,0 +1,175 @@
+use std::fs;
+
+use crate::scanner::Kind;
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+const LANGUAGES: [(&str, &[(&str, u32)]); 3] = [
+    (
+        "en",
+        &[
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ],
+    ),
+    (
+        "pt",
+        &[
+            ("um", 1),
+            ("uma", 1),
+            ("dois", 2),
+            ("duas", 2),
+            ("três", 3),
+            ("quatro", 4),
+            ("cinco", 5),
+            ("seis", 6),
+            ("sete", 7),
+            ("oito", 8),
+            ("nove", 9),
+        ],
+    ),
+    (
+        "es",
+        &[
+            ("uno", 1),
+            ("una", 1),
+            ("dos", 2),
+            ("tres", 3),
+            ("cuatro", 4),
+            ("cinco", 5),
+            ("seis", 6),
+            ("siete", 7),
+            ("ocho", 8),
+            ("nueve", 9),
+        ],
+    ),
+];
+
+// Zero isn't one of the puzzle's words, so it is only added on request.
+const ZERO_WORDS: [(&str, &str); 3] = [("en", "zero"), ("pt", "zero"), ("es", "cero")];
+
+// English teens, tens and multipliers, only used to read compound numbers.
+const COMPOUND_WORDS: [(&str, u32); 20] = [
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+    ("hundred", 100),
+    ("thousand", 1000),
+];
+
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Vocabulary {
+        Vocabulary { words: Vec::new() }
+    }
+
+    // The same word may come from several sources, but only for one value.
+    fn insert_word(&mut self, word: &str, value: u32) -> Result<(), String> {
+        if word.is_empty() {
+            return Err("Number words can't be empty".to_string());
+        }
+        match self.words.iter().find(|(known_word, _)| known_word == word) {
+            Some((_, known_value)) if *known_value != value => Err(format!(
+                "\"{}\" can't mean both {} and {}",
+                word, known_value, value
+            )),
+            Some(_) => Ok(()),
+            None => {
+                self.words.push((word.to_string(), value));
+                Ok(())
+            }
+        }
+    }
+
+    pub fn add_word(&mut self, word: &str, digit: u32) -> Result<(), String> {
+        if digit > 9 {
+            return Err(format!("{} isn't a digit, in \"{}\"", digit, word));
+        }
+        self.insert_word(word, digit)
+    }
+
+    pub fn add_compound_words(&mut self) -> Result<(), String> {
+        for (word, value) in COMPOUND_WORDS {
+            self.insert_word(word, value)?;
+        }
+        Ok(())
+    }
+
+    pub fn add_language(&mut self, code: &str, include_zero: bool) -> Result<(), String> {
+        let (_, words) = LANGUAGES
+            .iter()
+            .find(|(language, _)| *language == code)
+            .ok_or(format!("Unknown language: {}", code))?;
+        for (word, digit) in words.iter() {
+            self.add_word(word, *digit)?;
+        }
+        if include_zero {
+            for (_, word) in ZERO_WORDS.iter().filter(|(language, _)| *language == code) {
+                self.add_word(word, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    // One "word digit" pair per line. Blank lines and lines starting with '#'
+    // are skipped.
+    pub fn add_file(&mut self, file_path: &str) -> Result<(), String> {
+        let text = fs::read_to_string(file_path)
+            .map_err(|err| format!("Couldn't read {}: {}", file_path, err))?;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, digit) = line.rsplit_once(char::is_whitespace).ok_or(format!(
+                "{}:{}: expected \"word digit\"",
+                file_path,
+                i + 1
+            ))?;
+            let digit = digit
+                .parse()
+                .map_err(|_| format!("{}:{}: invalid digit {}", file_path, i + 1, digit))?;
+            self.add_word(word.trim(), digit)?;
+        }
+        Ok(())
+    }
+
+    // Plain digits always count, whatever the languages.
+    pub fn get_patterns(&self) -> Vec<(&str, u32, Kind)> {
+        let digits = DIGITS
+            .iter()
+            .enumerate()
+            .map(|(digit, text)| (*text, digit as u32, Kind::Numeral));
+        let words = self
+            .words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value, Kind::Word));
+        digits.chain(words).collect()
+    }
+}
@@ -3,6 +3,9 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+
 #[derive(Debug, Clone, PartialEq)]
 enum Direction {
     Above,
@@ -208,10 +211,15 @@ fn main() {
     // const FILE_PATH: &str = "smaller_input3.txt";
     // const FILE_PATH: &str = "smaller_input4.txt";
 
-    let plumping = read_input_file(FILE_PATH);
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+    let plumping = alloc_counter::measure("parse", || read_input_file(&file_path));
     let starting_pipe = find_starting_pipe(&plumping);
-    let steps_to_farthest_pipe = count_steps_to_farthest_pipe(&plumping, starting_pipe);
-    let pipes_on_each_side = count_pipes_on_each_side(&plumping, starting_pipe);
+    let steps_to_farthest_pipe = alloc_counter::measure("part one", || {
+        count_steps_to_farthest_pipe(&plumping, starting_pipe)
+    });
+    let pipes_on_each_side = alloc_counter::measure("part two", || {
+        count_pipes_on_each_side(&plumping, starting_pipe)
+    });
     println!("Starting position: {:?}", starting_pipe);
     println!("Steps to farthest pipe: {:?}", steps_to_farthest_pipe);
     println!("Pipes on each side: {:?}", pipes_on_each_side);
@@ -1,5 +1,8 @@
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Galaxy {
     x: usize,
@@ -81,21 +84,26 @@ fn main() {
     const FILE_PATH: &str = "input.txt";
     // const FILE_PATH: &str = "smaller_input.txt";
 
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+
     let universe_age_multiplier = 1;
-    let mut universe = read_input(FILE_PATH, universe_age_multiplier);
-    universe = universe.expand();
-    println!(
-        "Sum of relative distances for part one: {}",
-        add_all_relative_distances(&universe)
-    );
-
-    let universe_age_multiplier = 999_999;
-    let mut universe = read_input(FILE_PATH, universe_age_multiplier);
-    universe = universe.expand();
-    println!(
-        "Sum of relative distances for part two: {}",
-        add_all_relative_distances(&universe)
-    );
+    let older_universe_age_multiplier = 999_999;
+    let (universe, older_universe) = alloc_counter::measure("parse", || {
+        (
+            read_input(&file_path, universe_age_multiplier),
+            read_input(&file_path, older_universe_age_multiplier),
+        )
+    });
+
+    let sum = alloc_counter::measure("part one", || {
+        add_all_relative_distances(&universe.expand())
+    });
+    println!("Sum of relative distances for part one: {}", sum);
+
+    let sum = alloc_counter::measure("part two", || {
+        add_all_relative_distances(&older_universe.expand())
+    });
+    println!("Sum of relative distances for part two: {}", sum);
 }
 
 fn read_input(file_path: &str, age: u64) -> Universe {
@@ -116,7 +124,7 @@ fn add_all_relative_distances(universe: &Universe) -> u64 {
     let mut result = 0;
 
     let mut i = 0;
-    while i < universe.galaxies.len() - 1 {
+    while i + 1 < universe.galaxies.len() {
         for j in (i + 1)..universe.galaxies.len() {
             result += universe.galaxies[i].get_distance_to(&universe.galaxies[j]);
         }
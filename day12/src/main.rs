@@ -1,4 +1,9 @@
 use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::alloc_counter;
+use common::cli::Args;
+use common::thread_pool;
 
 mod tree;
 
@@ -25,19 +30,38 @@ fn main() {
     const FILE_PATH: &str = "input.txt";
     // const FILE_PATH: &str = "smaller_input.txt";
 
-    let springs = read_input_file(FILE_PATH);
+    let args = Args::parse(&["--threads"]);
+    let threads = args
+        .parse_option("--threads", 1)
+        .expect("Invalid number of threads");
+    let springs = alloc_counter::measure("parse", || {
+        read_input_file(&args.get_input_file_path(FILE_PATH))
+    });
     let total_records = springs.len();
 
-    let mut count = 0;
-    let mut root = tree::Node::new();
-    for (record_id, (spring_conditions, group_lengths)) in springs.into_iter().enumerate() {
-        print!(
-            "Analysing spring record number {} of {}. ({}%)\r",
-            record_id + 1,
-            total_records,
-            (record_id + 1) * 100 / total_records
-        );
-        count += root.insert(&spring_conditions, group_lengths);
-    }
+    // Records are counted once they are done, so with several threads the
+    // progress doesn't reach 100% while some are still being analysed.
+    let finished_records = AtomicUsize::new(0);
+    // Only part one is solved.
+    let count = alloc_counter::measure("part one", || {
+        thread_pool::par_map_reduce(
+            &springs,
+            threads,
+            |(spring_conditions, group_lengths)| {
+                let mut root = tree::Node::new();
+                let arrangement_count = root.insert(spring_conditions, group_lengths.clone());
+                let finished_count = finished_records.fetch_add(1, Ordering::Relaxed) + 1;
+                print!(
+                    "Analysed {} of {} spring records. ({}%)\r",
+                    finished_count,
+                    total_records,
+                    finished_count * 100 / total_records
+                );
+                arrangement_count
+            },
+            0,
+            |a, b| a + b,
+        )
+    });
     println!("\nSum of all possible arrangement counts: {}", count);
 }
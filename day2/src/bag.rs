@@ -0,0 +1,87 @@
+use std::fs;
+
+use crate::game::CubeSet;
+
+fn add_colour(bag: &mut CubeSet, colour: &str, count: &str) -> Result<(), String> {
+    if colour.is_empty() {
+        return Err("Colour names can't be empty".to_string());
+    }
+    let count = count
+        .parse()
+        .map_err(|err| format!("Invalid number of {} cubes {}: {}", colour, count, err))?;
+    if bag.counts.insert(colour.to_string(), count).is_some() {
+        return Err(format!("The bag lists {} twice", colour));
+    }
+    Ok(())
+}
+
+// Parses a bag given on the command line, such as "red=12,green=13".
+pub fn parse_bag(text: &str) -> Result<CubeSet, String> {
+    let mut bag = CubeSet::default();
+    for cubes in text.split(',').filter(|cubes| !cubes.trim().is_empty()) {
+        let (colour, count) = cubes
+            .split_once('=')
+            .ok_or(format!("Expected \"colour=count\", found \"{}\"", cubes))?;
+        add_colour(&mut bag, colour.trim(), count.trim())?;
+    }
+    Ok(bag)
+}
+
+// One "colour count" pair per line. Blank lines and lines starting with '#'
+// are skipped.
+pub fn read_bag_file(file_path: &str) -> Result<CubeSet, String> {
+    let text = fs::read_to_string(file_path)
+        .map_err(|err| format!("Couldn't read {}: {}", file_path, err))?;
+    let mut bag = CubeSet::default();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (colour, count) = line.rsplit_once(char::is_whitespace).ok_or(format!(
+            "{}:{}: expected \"colour count\"",
+            file_path,
+            i + 1
+        ))?;
+        add_colour(&mut bag, colour.trim(), count)
+            .map_err(|err| format!("{}:{}: {}", file_path, i + 1, err))?;
+    }
+    Ok(bag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_colours_and_counts() {
+        let bag = parse_bag(" red=12, green = 13,blue=14,").unwrap();
+        assert_eq!(bag.get_count("red"), 12);
+        assert_eq!(bag.get_count("green"), 13);
+        assert_eq!(bag.get_count("blue"), 14);
+        assert_eq!(bag.counts.len(), 3);
+    }
+
+    #[test]
+    fn an_empty_bag_holds_nothing() {
+        assert!(parse_bag("").unwrap().counts.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_colours() {
+        assert!(parse_bag("red").is_err());
+        assert!(parse_bag("=12").is_err());
+        assert!(parse_bag("red=12,red=3").is_err());
+    }
+
+    #[test]
+    fn rejects_counts_out_of_range() {
+        assert!(parse_bag("red=-1").is_err());
+        assert!(parse_bag("red=4294967296").is_err());
+        assert!(parse_bag("red=twelve").is_err());
+        assert_eq!(
+            parse_bag("red=4294967295").unwrap().get_count("red"),
+            u32::MAX
+        );
+    }
+}
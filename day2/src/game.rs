@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+
+// Number of cubes of each colour, either shown in one draw or held in a bag.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CubeSet {
+    pub counts: BTreeMap<String, u32>,
+}
+
+impl CubeSet {
+    // Parses a draw such as "3 blue, 4 red". A colour named twice adds up.
+    pub fn from(text: &str) -> Result<CubeSet, String> {
+        let mut cube_set = CubeSet::default();
+        for cubes in text.split(',').map(str::trim) {
+            let (count, colour) = cubes
+                .split_once(' ')
+                .ok_or(format!("Expected \"count colour\", found \"{}\"", cubes))?;
+            let count: u32 = count
+                .parse()
+                .map_err(|err| format!("Invalid number of cubes {}: {}", count, err))?;
+            let known_count = cube_set
+                .counts
+                .entry(colour.trim().to_string())
+                .or_insert(0);
+            *known_count = known_count.checked_add(count).ok_or(format!(
+                "Too many {} cubes in \"{}\"",
+                colour.trim(),
+                text.trim()
+            ))?;
+        }
+        Ok(cube_set)
+    }
+
+    pub fn get_count(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    // Product of the counts of every colour in the set. None if it doesn't
+    // fit in a u64.
+    pub fn get_power(&self) -> Option<u64> {
+        self.counts
+            .values()
+            .try_fold(1u64, |power, &count| power.checked_mul(count as u64))
+    }
+
+    // Every colour shown more often than the bag holds it, in alphabetical
+    // order. A colour missing from the bag has a limit of 0.
+    pub fn find_excesses(&self, bag: &CubeSet) -> Vec<&str> {
+        self.counts
+            .iter()
+            .filter(|(colour, &count)| count > bag.get_count(colour))
+            .map(|(colour, _)| colour.as_str())
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Game {
+    pub id: u64,
+    pub draws: Vec<CubeSet>,
+}
+
+impl Game {
+    // Parses a line such as "Game 1: 3 blue, 4 red; 1 red, 2 green".
+    pub fn from(line: &str) -> Result<Game, String> {
+        let (name, draws) = line
+            .split_once(':')
+            .ok_or(format!("Missing ':' in \"{}\"", line))?;
+        let id = name
+            .strip_prefix("Game ")
+            .ok_or(format!("Expected \"Game <id>\", found \"{}\"", name))?;
+        let id = id
+            .trim()
+            .parse()
+            .map_err(|err| format!("Invalid game id {}: {}", id, err))?;
+        let draws = draws
+            .split(';')
+            .map(CubeSet::from)
+            .collect::<Result<Vec<CubeSet>, String>>()?;
+        Ok(Game { id, draws })
+    }
+
+    // The fewest cubes of each colour that could have produced every draw.
+    pub fn get_minimum_cube_set(&self) -> CubeSet {
+        let mut minimum = CubeSet::default();
+        for draw in &self.draws {
+            for (colour, &count) in &draw.counts {
+                let known_count = minimum.counts.entry(colour.clone()).or_insert(0);
+                *known_count = (*known_count).max(count);
+            }
+        }
+        minimum
+    }
+
+    // Index of the first draw showing a colour the bag doesn't list, and
+    // that colour.
+    pub fn find_unknown_colour(&self, bag: &CubeSet) -> Option<(usize, &str)> {
+        self.draws.iter().enumerate().find_map(|(i, draw)| {
+            draw.counts
+                .keys()
+                .find(|colour| !bag.counts.contains_key(*colour))
+                .map(|colour| (i, colour.as_str()))
+        })
+    }
+
+    // Index of the first draw showing more cubes than the bag holds, and the
+    // colours it has too many of.
+    pub fn find_impossible_draw(&self, bag: &CubeSet) -> Option<(usize, Vec<&str>)> {
+        self.draws.iter().enumerate().find_map(|(i, draw)| {
+            let colours = draw.find_excesses(bag);
+            if colours.is_empty() {
+                None
+            } else {
+                Some((i, colours))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_cube_set(counts: &[(&str, u32)]) -> CubeSet {
+        CubeSet {
+            counts: counts
+                .iter()
+                .map(|&(colour, count)| (colour.to_string(), count))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn counts_above_255_are_accepted() {
+        let cube_set = CubeSet::from("300 red, 4294967295 blue").unwrap();
+        assert_eq!(cube_set, get_cube_set(&[("blue", u32::MAX), ("red", 300)]));
+    }
+
+    #[test]
+    fn counts_that_overflow_a_u32_are_rejected() {
+        assert!(CubeSet::from("4294967296 red").is_err());
+        assert!(CubeSet::from("-1 red").is_err());
+    }
+
+    #[test]
+    fn a_repeated_colour_adds_up() {
+        let cube_set = CubeSet::from("1 red, 2 blue, 3 red").unwrap();
+        assert_eq!(cube_set, get_cube_set(&[("blue", 2), ("red", 4)]));
+    }
+
+    #[test]
+    fn a_repeated_colour_that_overflows_is_rejected() {
+        assert!(CubeSet::from("4294967295 red, 1 red").is_err());
+    }
+
+    #[test]
+    fn game_ids_up_to_u64_max_are_accepted() {
+        let game = Game::from("Game 18446744073709551615: 1 red").unwrap();
+        assert_eq!(game.id, u64::MAX);
+    }
+
+    #[test]
+    fn game_ids_that_overflow_a_u64_are_rejected() {
+        assert!(Game::from("Game 18446744073709551616: 1 red").is_err());
+        assert!(Game::from("Game 99999999999999999999: 1 red").is_err());
+        assert!(Game::from("Game -1: 1 red").is_err());
+    }
+
+    #[test]
+    fn power_is_the_product_of_every_count() {
+        let game = Game::from("Game 1: 3 blue, 4 red; 2 green, 6 blue; 2 purple").unwrap();
+        assert_eq!(game.get_minimum_cube_set().get_power(), Some(96));
+    }
+
+    #[test]
+    fn power_that_overflows_a_u64_is_none() {
+        let cube_set = CubeSet::from("4294967295 red, 4294967295 green, 2 blue").unwrap();
+        assert_eq!(cube_set.get_power(), None);
+        let cube_set = CubeSet::from("4294967295 red, 4294967295 green").unwrap();
+        assert_eq!(cube_set.get_power(), Some(18446744065119617025));
+    }
+
+    #[test]
+    fn splits_draws_on_semicolons_and_cubes_on_commas() {
+        let game = Game::from("Game 3: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(
+            game.draws,
+            vec![
+                get_cube_set(&[("blue", 3), ("red", 4)]),
+                get_cube_set(&[("blue", 6), ("green", 2), ("red", 1)]),
+                get_cube_set(&[("green", 2)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_extra_whitespace() {
+        let game = Game::from("Game  12 :  3  blue ,4 red ;   1 green  ").unwrap();
+        assert_eq!(game.id, 12);
+        assert_eq!(
+            game.draws,
+            vec![
+                get_cube_set(&[("blue", 3), ("red", 4)]),
+                get_cube_set(&[("green", 1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_an_empty_draw() {
+        assert!(Game::from("Game 1: 3 blue;; 2 red").is_err());
+        assert!(Game::from("Game 1: 3 blue, ; 2 red").is_err());
+        assert!(Game::from("Game 1:").is_err());
+    }
+
+    #[test]
+    fn rejects_a_missing_colon() {
+        assert!(Game::from("Game 1 3 blue, 4 red").is_err());
+    }
+
+    #[test]
+    fn rejects_a_missing_game_name() {
+        assert!(Game::from("1: 3 blue").is_err());
+        assert!(Game::from("Game: 3 blue").is_err());
+    }
+
+    #[test]
+    fn finds_the_first_impossible_draw() {
+        let bag = get_cube_set(&[("blue", 14), ("green", 13), ("red", 12)]);
+        let game = Game::from("Game 4: 1 green, 3 red; 3 green, 15 blue, 14 red; 20 red").unwrap();
+        assert_eq!(
+            game.find_impossible_draw(&bag),
+            Some((1, vec!["blue", "red"]))
+        );
+
+        let game = Game::from("Game 2: 1 blue, 2 green; 12 red, 13 green, 14 blue").unwrap();
+        assert_eq!(game.find_impossible_draw(&bag), None);
+    }
+
+    #[test]
+    fn a_colour_missing_from_the_bag_makes_a_draw_impossible() {
+        let bag = get_cube_set(&[("blue", 14), ("green", 13), ("red", 12)]);
+        let game = Game::from("Game 1: 3 blue; 2 green, 1 purple").unwrap();
+        assert_eq!(game.find_impossible_draw(&bag), Some((1, vec!["purple"])));
+        assert_eq!(game.find_unknown_colour(&bag), Some((1, "purple")));
+    }
+}
@@ -1,67 +1,120 @@
-use regex::Regex;
 use std::fs;
+use std::process;
+
+use common::alloc_counter;
+use common::cli::Args;
+use common::thread_pool;
+
+mod bag;
+mod game;
+mod report;
+
+use game::{CubeSet, Game};
 
 const INPUT_FILEPATH: &str = "input.txt";
-const MAX_RED_PERMITTED: u8 = 12;
-const MAX_BLUE_PERMITTED: u8 = 14;
-const MAX_GREEN_PERMITTED: u8 = 13;
+// A fourth colour. The sums are 2 and 1806, and --strict fails on the purple
+// cubes of game 1. With --bag-file four_colour_bag.txt the sum of ids is 8.
+// const INPUT_FILEPATH: &str = "colours_input.txt";
+const DEFAULT_BAG: &str = "red=12,green=13,blue=14";
 
-fn read_input_file() -> Vec<String> {
-    fs::read_to_string(INPUT_FILEPATH)
-        .expect("Couldn't read input file")
-        .lines()
-        .map(String::from)
-        .collect()
+fn read_input_file(file_path: &str) -> Result<Vec<String>, String> {
+    let contents = fs::read_to_string(file_path)
+        .map_err(|err| format!("Couldn't read input file {}: {}", file_path, err))?;
+    Ok(contents.lines().map(String::from).collect())
 }
 
-fn get_matches(text: &str, pattern: &str) -> Vec<String> {
-    let re = Regex::new(pattern).unwrap();
-    re.captures_iter(text)
-        .flat_map(|c| c.iter().skip(1).map(|m| m.unwrap().as_str().to_string()).collect::<Vec<String>>())
-        .collect()
+fn get_bag(args: &Args) -> Result<CubeSet, String> {
+    match (args.get_option("--bag"), args.get_option("--bag-file")) {
+        (Some(_), Some(_)) => Err("--bag and --bag-file can't be used together".to_string()),
+        (Some(text), None) => bag::parse_bag(text),
+        (None, Some(file_path)) => bag::read_bag_file(file_path),
+        (None, None) => bag::parse_bag(DEFAULT_BAG),
+    }
 }
 
-fn get_game_records(line: &str) -> (u8, Vec<u8>, Vec<u8>, Vec<u8>) {
-    let game_id_matches = get_matches(line, r"^Game (.+):");
-    let red_matches = get_matches(line, r" ([0-9]+) red");
-    let blue_matches = get_matches(line, r" ([0-9]+) blue");
-    let green_matches = get_matches(line, r" ([0-9]+) green");
+// A colour the bag doesn't list has a limit of 0, so the game is impossible.
+// With --strict it is rejected as invalid input instead.
+fn get_game(line: &str, bag: &CubeSet, is_strict: bool) -> Result<Game, String> {
+    let game =
+        Game::from(line).map_err(|err| format!("Invalid game record \"{}\": {}", line, err))?;
+    if is_strict {
+        if let Some((draw, colour)) = game.find_unknown_colour(bag) {
+            return Err(format!(
+                "Game {}, draw {}: the bag has no {} cubes",
+                game.id,
+                draw + 1,
+                colour
+            ));
+        }
+    }
+    Ok(game)
+}
 
-    (
-        game_id_matches.iter().map(|c| c.parse().unwrap()).next().unwrap(),
-        red_matches.iter().map(|c| c.parse().unwrap()).collect(),
-        blue_matches.iter().map(|c| c.parse().unwrap()).collect(),
-        green_matches.iter().map(|c| c.parse().unwrap()).collect(),
-    )
+fn get_power(game: &Game) -> Result<u64, String> {
+    game.get_minimum_cube_set().get_power().ok_or(format!(
+        "Game {}: the power of its minimum cube set overflowed a u64",
+        game.id
+    ))
 }
 
-fn get_max_value(values: Vec<u8>) -> u8 {
-    let mut max = 0;
-    for value in values {
-        if value > max {
-            max = value;
-        }
+fn get_id_if_possible_and_power(
+    line: &str,
+    bag: &CubeSet,
+    is_strict: bool,
+) -> Result<(u64, u64), String> {
+    let game = get_game(line, bag, is_strict)?;
+    let power = get_power(&game)?;
+
+    if game.find_impossible_draw(bag).is_some() {
+        return Ok((0, power));
     }
-    max
+    Ok((game.id, power))
 }
 
-fn main() {
-    let lines = read_input_file();
-    let mut sum_of_ids = 0;
-    let mut sum_of_powers = 0;
-    for line in lines {
-        let (id, red, blue, green) = get_game_records(&line);
-        let max_red_found = get_max_value(red);
-        let max_blue_found = get_max_value(blue);
-        let max_green_found = get_max_value(green);
+fn add_sums(a: (u64, u64), b: (u64, u64)) -> Result<(u64, u64), String> {
+    Ok((
+        a.0.checked_add(b.0).ok_or("Sum of ids overflowed a u64")?,
+        a.1.checked_add(b.1)
+            .ok_or("Sum of powers overflowed a u64")?,
+    ))
+}
 
-        sum_of_powers += max_red_found as u32 * max_green_found as u32 * max_blue_found as u32;
+fn run(args: &Args) -> Result<(), String> {
+    let threads = args.parse_option("--threads", 1)?;
+    let lines = alloc_counter::measure("read", || {
+        read_input_file(&args.get_input_file_path(INPUT_FILEPATH))
+    })?;
+    let bag = get_bag(args).map_err(|err| format!("Invalid bag: {}", err))?;
+    let is_strict = args.has_flag("--strict");
 
-        if max_red_found > MAX_RED_PERMITTED || max_blue_found > MAX_BLUE_PERMITTED || max_green_found > MAX_GREEN_PERMITTED {
-            continue;
-        }
-        sum_of_ids += id as u16;
+    if args.has_flag("--report") {
+        return match args.get_option("--format").unwrap_or("table") {
+            "table" => report::print_table(&lines, &bag, is_strict),
+            "json" => report::print_json(&lines, &bag, is_strict),
+            format => Err(format!("Unknown report format: {}", format)),
+        };
     }
+
+    // Each game is parsed and counted towards both parts in the same pass.
+    let (sum_of_ids, sum_of_powers) = alloc_counter::measure("parse and both parts", || {
+        thread_pool::par_map_reduce(
+            &lines,
+            threads,
+            |line| get_id_if_possible_and_power(line, &bag, is_strict),
+            Ok((0, 0)),
+            |a, b| add_sums(a?, b?),
+        )
+    })?;
     println!("sum of ids: {}", sum_of_ids);
     println!("power of minimmum cubes: {}", sum_of_powers);
+    Ok(())
+}
+
+// Bad input is reported and rejected with exit code 1 rather than a panic.
+fn main() {
+    let args = Args::parse(&["--threads", "--bag", "--bag-file", "--format"]);
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,121 @@
+use common::json::Json;
+
+use crate::game::{CubeSet, Game};
+use crate::{add_sums, get_game, get_power};
+
+fn format_cube_set(cube_set: &CubeSet) -> String {
+    let cubes: Vec<String> = cube_set
+        .counts
+        .iter()
+        .map(|(colour, count)| format!("{} {}", count, colour))
+        .collect();
+    cubes.join(", ")
+}
+
+fn get_cube_set_json(cube_set: &CubeSet) -> Json {
+    Json::Object(
+        cube_set
+            .counts
+            .iter()
+            .map(|(colour, count)| (colour.clone(), Json::number(count)))
+            .collect(),
+    )
+}
+
+// Draws are numbered from 1, the way they read in the input.
+fn format_excess(game: &Game, bag: &CubeSet) -> String {
+    match game.find_impossible_draw(bag) {
+        Some((draw, colours)) => {
+            let excesses: Vec<String> = colours
+                .iter()
+                .map(|colour| {
+                    format!(
+                        "{} {} > {}",
+                        game.draws[draw].get_count(colour),
+                        colour,
+                        bag.get_count(colour)
+                    )
+                })
+                .collect();
+            format!("draw {}: {}", draw + 1, excesses.join(", "))
+        }
+        None => "-".to_string(),
+    }
+}
+
+fn get_excess_json(game: &Game, bag: &CubeSet) -> Json {
+    match game.find_impossible_draw(bag) {
+        Some((draw, colours)) => Json::object(vec![
+            ("draw", Json::number(draw + 1)),
+            (
+                "colours",
+                Json::Array(
+                    colours
+                        .iter()
+                        .map(|colour| {
+                            Json::object(vec![
+                                ("colour", Json::string(colour)),
+                                ("shown", Json::number(game.draws[draw].get_count(colour))),
+                                ("limit", Json::number(bag.get_count(colour))),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ]),
+        None => Json::Null,
+    }
+}
+
+pub fn print_table(lines: &[String], bag: &CubeSet, is_strict: bool) -> Result<(), String> {
+    println!(
+        "{:>6}  {:<8}  {:<40}  {:>12}  Minimum cube set",
+        "Game", "Possible", "Exceeded", "Power"
+    );
+    let mut sums = (0, 0);
+    for line in lines {
+        let game = get_game(line, bag, is_strict)?;
+        let power = get_power(&game)?;
+        let is_possible = game.find_impossible_draw(bag).is_none();
+        sums = add_sums(sums, (if is_possible { game.id } else { 0 }, power))?;
+
+        println!(
+            "{:>6}  {:<8}  {:<40}  {:>12}  {}",
+            game.id,
+            if is_possible { "yes" } else { "no" },
+            format_excess(&game, bag),
+            power,
+            format_cube_set(&game.get_minimum_cube_set())
+        );
+    }
+    println!("sum of ids: {}", sums.0);
+    println!("power of minimmum cubes: {}", sums.1);
+    Ok(())
+}
+
+pub fn print_json(lines: &[String], bag: &CubeSet, is_strict: bool) -> Result<(), String> {
+    let mut sums = (0, 0);
+    let mut games = Vec::new();
+    for line in lines {
+        let game = get_game(line, bag, is_strict)?;
+        let power = get_power(&game)?;
+        let is_possible = game.find_impossible_draw(bag).is_none();
+        sums = add_sums(sums, (if is_possible { game.id } else { 0 }, power))?;
+
+        games.push(Json::object(vec![
+            ("id", Json::number(game.id)),
+            ("possible", Json::Bool(is_possible)),
+            ("exceeded", get_excess_json(&game, bag)),
+            ("minimum", get_cube_set_json(&game.get_minimum_cube_set())),
+            ("power", Json::number(power)),
+        ]));
+    }
+    let report = Json::object(vec![
+        ("bag", get_cube_set_json(bag)),
+        ("games", Json::Array(games)),
+        ("sum_of_ids", Json::number(sums.0)),
+        ("sum_of_powers", Json::number(sums.1)),
+    ]);
+    println!("{}", report);
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+
 const INPUT_FILE_PATH: &str = "input.txt";
 // const INPUT_FILE_PATH: &str = "smaller_input.txt";
 
 fn read_input_file() -> Vec<String> {
-    fs::read_to_string(INPUT_FILE_PATH)
+    let file_path = Args::parse(&[]).get_input_file_path(INPUT_FILE_PATH);
+    fs::read_to_string(file_path)
         .expect("Couldn't read input file")
         .lines()
         .map(String::from)
@@ -69,8 +73,7 @@ fn get_numbers_adjacent_to_symbol(x: usize, y: usize, lines: &Vec<String>, map:
     let mut numbers_adjacent_to_symbol = Vec::new();
     let mut used_ids = Vec::new();
     for (candidate_y, candidate_x) in candidates_coords {
-        if let Some(candidate_line) = map.get(candidate_y) {
-            let digit_id = candidate_line[candidate_x];
+        if let Some(&digit_id) = map.get(candidate_y).and_then(|line| line.get(candidate_x)) {
             if used_ids.contains(&digit_id) || digit_id == 0 || digit_id == -1 {
                 continue;
             }
@@ -97,28 +100,39 @@ fn get_number_by_id(target_id: i32, lines: &Vec<String>, map: &Vec<Vec<i32>>) ->
 }
 
 fn main() {
-    let lines = read_input_file();
-    let map = build_map(&lines);
-    let adjacent_numbers = get_numbers_adjacent_to_symbols(&lines, &map);
+    let adjacent_numbers = alloc_counter::measure("parse", || {
+        let lines = read_input_file();
+        let map = build_map(&lines);
+        get_numbers_adjacent_to_symbols(&lines, &map)
+    });
 
-    let mut sum_of_the_gear_ratios = 0;
-    let mut sum_of_the_part_numbers = 0;
-    let mut used_ids = Vec::new();
-    let mut testing_something = 0;
-    for (numbers, symbol) in adjacent_numbers {
-        if symbol == '*' && numbers.len() == 2 {
-            sum_of_the_gear_ratios += numbers[0].1*numbers[1].1;
-        }
-        for (id, num) in numbers {
-            if !(used_ids.contains(&id)) {
-                sum_of_the_part_numbers += num;
-                used_ids.push(id);
+    let (sum_of_the_part_numbers, testing_something) = alloc_counter::measure("part one", || {
+        let mut sum_of_the_part_numbers = 0;
+        let mut used_ids = Vec::new();
+        let mut testing_something = 0;
+        for (numbers, _) in &adjacent_numbers {
+            for &(id, num) in numbers {
+                if !(used_ids.contains(&id)) {
+                    sum_of_the_part_numbers += num;
+                    used_ids.push(id);
+                }
+                else {
+                    testing_something += num;
+                }
             }
-            else {
-                testing_something += num;
+        }
+        (sum_of_the_part_numbers, testing_something)
+    });
+
+    let sum_of_the_gear_ratios = alloc_counter::measure("part two", || {
+        let mut sum_of_the_gear_ratios = 0;
+        for (numbers, symbol) in &adjacent_numbers {
+            if *symbol == '*' && numbers.len() == 2 {
+                sum_of_the_gear_ratios += numbers[0].1*numbers[1].1;
             }
         }
-    }
+        sum_of_the_gear_ratios
+    });
     println!("Sum of the gear ratios: {}", sum_of_the_gear_ratios);
     println!("Sum of the part numbers: {}", sum_of_the_part_numbers);
     println!("Test: {}", testing_something);
@@ -1,11 +1,15 @@
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+
 const FILE_PATH: &str = "input.txt";
 // const FILE_PATH: &str = "smaller_input.txt";
 
 
 fn read_input_file() -> Vec<(u32, Vec<u32>, Vec<u32>)> {
-    fs::read_to_string(FILE_PATH)
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+    fs::read_to_string(file_path)
         .expect("Couldn't read input file")
         .lines()
         .map(remove_unnecessary_stuff)
@@ -39,27 +43,40 @@ fn add_count(line: (Vec<u32>, Vec<u32>)) -> (u32, Vec<u32>, Vec<u32>) {
     (1, line.0, line.1)
 }
 
+fn count_matching_numbers(winning_numbers: &Vec<u32>, my_numbers: &Vec<u32>) -> usize {
+    winning_numbers
+        .into_iter()
+        .filter(|n| my_numbers.contains(&n))
+        .count()
+}
+
 fn main() {
-    let lines = read_input_file();
-    let mut lines_copy = lines.clone();
+    let lines = alloc_counter::measure("parse", read_input_file);
 
-    let mut total_points = 0;
-    for (i, (_, winning_numbers, my_numbers)) in lines.iter().enumerate() {
-        let matching_numbers: Vec<u32> = winning_numbers
-            .into_iter()
-            .filter(|n| my_numbers.contains(&n))
-            .map(|&n| n)
-            .collect();
-        if matching_numbers.len() > 0 {
-            total_points += 2u32.pow(matching_numbers.len() as u32 - 1);
+    let total_points = alloc_counter::measure("part one", || {
+        let mut total_points = 0;
+        for (_, winning_numbers, my_numbers) in &lines {
+            let matching_numbers = count_matching_numbers(winning_numbers, my_numbers);
+            if matching_numbers > 0 {
+                total_points += 2u32.pow(matching_numbers as u32 - 1);
+            }
         }
+        total_points
+    });
 
-
-        let multiplier = lines_copy[i].0;
-        for card_index in i+1..(i+1 + matching_numbers.len()) {
-            lines_copy[card_index].0 += multiplier;
+    let total_cards = alloc_counter::measure("part two", || {
+        let mut lines_copy = lines.clone();
+        for (i, (_, winning_numbers, my_numbers)) in lines.iter().enumerate() {
+            let matching_numbers = count_matching_numbers(winning_numbers, my_numbers);
+            let multiplier = lines_copy[i].0;
+            for card_index in i+1..(i+1 + matching_numbers) {
+                if let Some(card) = lines_copy.get_mut(card_index) {
+                    card.0 += multiplier;
+                }
+            }
         }
-    }
-    println!("Total cards: {:?}", lines_copy.iter().fold(0, |sum, item| sum + &item.0));
+        lines_copy.iter().fold(0, |sum, item| sum + &item.0)
+    });
+    println!("Total cards: {:?}", total_cards);
     println!("Total points: {:?}", total_points);
 }
@@ -3,6 +3,9 @@ use std::collections::HashMap;
 use std::fs;
 use std::time::Instant;
 
+use common::alloc_counter;
+use common::cli::Args;
+
 const FILE_PATH: &str = "input.txt";
 // const FILE_PATH: &str = "smaller_input.txt";
 
@@ -19,7 +22,8 @@ const SECTION_NAMES: [&str; 8] = [
 ];
 
 fn read_input_file() -> Vec<(String, String)> {
-    fs::read_to_string(FILE_PATH)
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+    fs::read_to_string(file_path)
         .unwrap()
         .split("\n\n")
         .zip(SECTION_NAMES)
@@ -294,13 +298,19 @@ fn get_lowest_location(range_sets: HashMap<String, RangeSet>) -> u64 {
 }
 
 fn main() {
-    let sections = read_input_file();
-    let range_sets_pt1 = get_range_sets_for_pt1(&sections);
-    let range_sets_pt2 = get_range_sets_for_pt2(&sections);
+    let (range_sets_pt1, range_sets_pt2) = alloc_counter::measure("parse", || {
+        let sections = read_input_file();
+        (
+            get_range_sets_for_pt1(&sections),
+            get_range_sets_for_pt2(&sections),
+        )
+    });
 
     let before = Instant::now();
-    let lowest_location_pt1 = get_lowest_location(range_sets_pt1);
-    let lowest_location_pt2 = get_lowest_location(range_sets_pt2);
+    let lowest_location_pt1 =
+        alloc_counter::measure("part one", || get_lowest_location(range_sets_pt1));
+    let lowest_location_pt2 =
+        alloc_counter::measure("part two", || get_lowest_location(range_sets_pt2));
     println!("Lowest location for part one: {}", lowest_location_pt1);
     println!("Lowest location for part two: {}", lowest_location_pt2);
     println!("Calculated in {:.2?}", before.elapsed());
@@ -1,3 +1,33 @@
+use std::fs;
+
+use common::alloc_counter;
+use common::cli::Args;
+
+const FILE_PATH: &str = "input.txt";
+// const FILE_PATH: &str = "smaller_input.txt";
+
+fn read_input_file() -> Vec<String> {
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+    fs::read_to_string(file_path)
+        .expect("Couldn't read input file")
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+fn get_values(line: &str) -> Vec<u64> {
+    let (_, values) = line.split_once(":").unwrap();
+    values
+        .split_whitespace()
+        .map(|value| value.parse().unwrap())
+        .collect()
+}
+
+fn get_value_ignoring_spaces(line: &str) -> Vec<u64> {
+    let (_, values) = line.split_once(":").unwrap();
+    vec![values.replace(" ", "").parse().unwrap()]
+}
+
 struct Race {
     time: u64,
     record_distance: u64,
@@ -36,23 +66,23 @@ fn get_number_of_ways_i_could_beat_the_record(races: Vec<Race>) -> u32 {
 }
 
 fn main() {
-    let times = vec![59, 70, 78, 78];
-    let distances = vec![430, 1218, 1213, 1276];
+    let lines = alloc_counter::measure("parse", read_input_file);
 
-    // let times = vec![7, 15, 30];
-    // let distances = vec![9, 40, 200];
+    let ways = alloc_counter::measure("part one", || {
+        let times = get_values(&lines[0]);
+        let distances = get_values(&lines[1]);
 
-    let races = Race::from(times, distances);
-    let ways = get_number_of_ways_i_could_beat_the_record(races);
+        let races = Race::from(times, distances);
+        get_number_of_ways_i_could_beat_the_record(races)
+    });
     println!("Part one solution: {}", ways);
 
-    // let times = vec![71530];
-    // let distances = vec![940200];
-
-    let times = vec![59707878];
-    let distances = vec![430121812131276];
+    let ways = alloc_counter::measure("part two", || {
+        let times = get_value_ignoring_spaces(&lines[0]);
+        let distances = get_value_ignoring_spaces(&lines[1]);
 
-    let races = Race::from(times, distances);
-    let ways = get_number_of_ways_i_could_beat_the_record(races);
+        let races = Race::from(times, distances);
+        get_number_of_ways_i_could_beat_the_record(races)
+    });
     println!("Part two solution: {}", ways);
 }
@@ -1,11 +1,15 @@
 use std::cmp::Ordering;
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+
 const FILE_PATH: &str = "input.txt";
 // const FILE_PATH: &str = "smaller_input.txt";
 
 fn read_input_file() -> Vec<Hand> {
-    let contents = fs::read_to_string(FILE_PATH)
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+    let contents = fs::read_to_string(file_path)
         .unwrap_or_else(|err| panic!("Couldn't read input file: {}", err));
 
     let hands: Vec<Hand> = contents
@@ -145,12 +149,15 @@ impl HandType {
     }
 }
 
+// Only part one is solved.
 fn main() {
-    let mut hands = read_input_file();
-    hands.sort_by(|hand, other_hand| hand.clone().compare_to(&other_hand));
-    let total_winnings = hands
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (idx, hand)| acc + (idx as u32 + 1) * hand.bid);
+    let mut hands = alloc_counter::measure("parse", read_input_file);
+    let total_winnings = alloc_counter::measure("part one", || {
+        hands.sort_by(|hand, other_hand| hand.clone().compare_to(&other_hand));
+        hands
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (idx, hand)| acc + (idx as u32 + 1) * hand.bid)
+    });
     println!("{:?}", total_winnings);
 }
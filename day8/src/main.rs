@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+use common::number_theory;
+
 const FILE_PATH: &str = "input.txt";
 // const FILE_PATH: &str = "smaller_input.txt";
 // const FILE_PATH: &str = "smaller_input2.txt";
 
 fn read_input_file() -> Vec<String> {
-    fs::read_to_string(FILE_PATH)
+    let file_path = Args::parse(&[]).get_input_file_path(FILE_PATH);
+    fs::read_to_string(file_path)
         .unwrap()
         .lines()
         .map(String::from)
@@ -112,47 +117,31 @@ fn count_steps_until_zzz_for_part_two(
             }
             new_current_parallel_maps[i] = new_map;
             if cycles.len() == current_parallel_maps.len() {
-                return lcm_of_vector_elements(&cycles).unwrap();
+                return get_steps_until_cycles_align(&cycles);
             }
         }
         current_parallel_maps = new_current_parallel_maps;
         steps+=1;
     }
-    return lcm_of_vector_elements(&cycles).unwrap();
-}
-
-fn gcd(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
-}
-
-fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
+    return get_steps_until_cycles_align(&cycles);
 }
 
-
-fn lcm_of_vector_elements(vec: &[u64]) -> Option<u64> {
-    if vec.is_empty() {
-        return None; // Handle empty vectors
-    }
-
-    let mut result = vec[0];
-    for &element in &vec[1..] {
-        result = lcm(result, element);
-    }
-
-    Some(result)
+fn get_steps_until_cycles_align(cycles: &[u64]) -> u64 {
+    number_theory::checked_lcm_of(cycles.iter().copied())
+        .expect("Couldn't align the cycles within u64 steps")
 }
 
 fn main() {
-    let lines = read_input_file();
-    let instructions = get_instructions(&lines);
-    let directions = get_directions(&lines);
-    let steps_count = count_steps_until_zzz(&instructions, &directions);
+    let (instructions, directions) = alloc_counter::measure("parse", || {
+        let lines = read_input_file();
+        (get_instructions(&lines), get_directions(&lines))
+    });
+    let steps_count = alloc_counter::measure("part one", || {
+        count_steps_until_zzz(&instructions, &directions)
+    });
     println!("Part one solution: {}", steps_count);
-    let steps_count_part_two = count_steps_until_zzz_for_part_two(&instructions, &directions);
+    let steps_count_part_two = alloc_counter::measure("part two", || {
+        count_steps_until_zzz_for_part_two(&instructions, &directions)
+    });
     println!("Part two solution: {}", steps_count_part_two);
 }
@@ -1,10 +1,14 @@
 use std::fs;
 
+use common::alloc_counter;
+use common::cli::Args;
+use common::thread_pool;
+
 const FILE_PATH: &str = "input.txt";
 // const FILE_PATH: &str = "smaller_input.txt";
 
-fn read_input_file() -> Vec<Vec<i64>> {
-    fs::read_to_string(FILE_PATH)
+fn read_input_file(file_path: &str) -> Vec<Vec<i64>> {
+    fs::read_to_string(file_path)
         .expect("Couldn't read input file")
         .lines()
         .map(|line| {
@@ -39,26 +43,25 @@ fn get_differences(values: &Vec<i64>) -> Vec<i64> {
     new_values
 }
 
-fn sum_extrapolations(lines: &Vec<Vec<i64>>) -> i64 {
-    let mut result = 0;
-    for line in lines {
-        result += extrapolate(line);
-    }
-    result
+fn sum_extrapolations(lines: &[Vec<i64>], threads: usize) -> i64 {
+    thread_pool::par_map_reduce(lines, threads, extrapolate, 0, |a, b| a + b)
 }
 
-fn sum_backward_extrapolations(lines: &Vec<Vec<i64>>) -> i64 {
-    let mut result = 0;
-    for line in lines {
-        result += extrapolate_backwards(line);
-    }
-    result
+fn sum_backward_extrapolations(lines: &[Vec<i64>], threads: usize) -> i64 {
+    thread_pool::par_map_reduce(lines, threads, extrapolate_backwards, 0, |a, b| a + b)
 }
 
 fn main() {
-    let lines = read_input_file();
-    let result = sum_extrapolations(&lines);
+    let args = Args::parse(&["--threads"]);
+    let threads = args
+        .parse_option("--threads", 1)
+        .expect("Invalid number of threads");
+    let lines = alloc_counter::measure("parse", || {
+        read_input_file(&args.get_input_file_path(FILE_PATH))
+    });
+    let result = alloc_counter::measure("part one", || sum_extrapolations(&lines, threads));
     println!("Part one: {result:?}");
-    let result = sum_backward_extrapolations(&lines);
+    let result =
+        alloc_counter::measure("part two", || sum_backward_extrapolations(&lines, threads));
     println!("Part two: {result:?}");
 }
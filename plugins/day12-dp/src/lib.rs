@@ -0,0 +1,127 @@
+use std::ffi::{c_char, c_void, CString};
+use std::panic;
+use std::slice;
+
+type SolveFn = unsafe extern "C" fn(input: *const u8, input_len: usize) -> *mut c_char;
+type RegisterFn = unsafe extern "C" fn(
+    context: *mut c_void,
+    day: u32,
+    part: u32,
+    name: *const c_char,
+    solve: SolveFn,
+);
+
+const NAME: &[u8] = b"day12-dp\0";
+const UNFOLD_COPIES: usize = 5;
+
+fn parse_record(line: &str) -> Option<(Vec<u8>, Vec<usize>)> {
+    let (conditions, lengths) = line.split_once(' ')?;
+    if !conditions
+        .bytes()
+        .all(|byte| matches!(byte, b'.' | b'#' | b'?'))
+    {
+        return None;
+    }
+    let lengths = lengths
+        .split(',')
+        .map(|length| length.parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    Some((conditions.as_bytes().to_vec(), lengths))
+}
+
+fn unfold(conditions: &[u8], lengths: &[usize]) -> (Vec<u8>, Vec<usize>) {
+    let mut unfolded_conditions = conditions.to_vec();
+    for _ in 1..UNFOLD_COPIES {
+        unfolded_conditions.push(b'?');
+        unfolded_conditions.extend_from_slice(conditions);
+    }
+    (unfolded_conditions, lengths.repeat(UNFOLD_COPIES))
+}
+
+// arrangements[i][j] counts the ways conditions[i..] can hold the groups in
+// lengths[j..], filled from the end of the record towards its start.
+fn count_arrangements(conditions: &[u8], lengths: &[usize]) -> u64 {
+    let (n, m) = (conditions.len(), lengths.len());
+    let mut arrangements = vec![vec![0u64; m + 1]; n + 1];
+    arrangements[n][m] = 1;
+
+    for i in (0..n).rev() {
+        for j in 0..=m {
+            let mut count = 0;
+            if conditions[i] != b'#' {
+                count += arrangements[i + 1][j];
+            }
+            if conditions[i] != b'.' && j < m {
+                let end = i + lengths[j];
+                let fits = end <= n
+                    && !conditions[i..end].contains(&b'.')
+                    && conditions.get(end) != Some(&b'#');
+                if fits {
+                    count += arrangements[(end + 1).min(n)][j + 1];
+                }
+            }
+            arrangements[i][j] = count;
+        }
+    }
+    arrangements[0][0]
+}
+
+fn solve(input: &str, should_unfold: bool) -> Option<u64> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (conditions, lengths) = parse_record(line)?;
+            Some(match should_unfold {
+                true => {
+                    let (conditions, lengths) = unfold(&conditions, &lengths);
+                    count_arrangements(&conditions, &lengths)
+                }
+                false => count_arrangements(&conditions, &lengths),
+            })
+        })
+        .try_fold(0u64, |total, count| total.checked_add(count?))
+}
+
+// Panics must not unwind into the runner, so they end up as a NULL answer.
+unsafe fn solve_raw(input: *const u8, input_len: usize, should_unfold: bool) -> *mut c_char {
+    let input = slice::from_raw_parts(input, input_len);
+    let answer = panic::catch_unwind(|| {
+        let input = std::str::from_utf8(input).ok()?;
+        solve(input, should_unfold)
+    });
+    match answer {
+        Ok(Some(answer)) => CString::new(answer.to_string())
+            .expect("Numbers have no NUL bytes")
+            .into_raw(),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn solve_part_one(input: *const u8, input_len: usize) -> *mut c_char {
+    solve_raw(input, input_len, false)
+}
+
+unsafe extern "C" fn solve_part_two(input: *const u8, input_len: usize) -> *mut c_char {
+    solve_raw(input, input_len, true)
+}
+
+/// # Safety
+///
+/// `register` must be safe to call with `context` and a NUL-terminated name.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_plugin_register(context: *mut c_void, register: RegisterFn) {
+    let name = NAME.as_ptr() as *const c_char;
+    register(context, 12, 1, name, solve_part_one);
+    register(context, 12, 2, name, solve_part_two);
+}
+
+/// # Safety
+///
+/// `answer` must have been returned by one of this plugin's solve functions.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_plugin_free(answer: *mut c_char) {
+    if !answer.is_null() {
+        drop(CString::from_raw(answer));
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+use crate::history;
+use crate::solver::{self, TimedLine};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 20;
+
+const CLEAR_LINE: &str = "\x1b[2K";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+
+#[derive(Debug, Clone)]
+enum State {
+    Pending,
+    Building,
+    Running(Instant),
+    Done,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+struct DayStatus {
+    state: State,
+    answers: Vec<solver::Answer>,
+    // The last "(NN%)" the day printed, for days that report their own progress.
+    reported_progress: Option<f64>,
+}
+
+impl DayStatus {
+    fn new() -> DayStatus {
+        DayStatus {
+            state: State::Pending,
+            answers: Vec::new(),
+            reported_progress: None,
+        }
+    }
+}
+
+struct Row {
+    day: u8,
+    part: u8,
+    state: &'static str,
+    color: &'static str,
+    progress: Option<f64>,
+    elapsed: Option<Duration>,
+    detail: String,
+}
+
+fn get_reported_progress(text: &str) -> Option<f64> {
+    let end = text.rfind("%)")?;
+    let start = text[..end].rfind('(')?;
+    let percentage: f64 = text[start + 1..end].trim().parse().ok()?;
+    Some((percentage / 100.0).clamp(0.0, 1.0))
+}
+
+// Median past timings of every day and part on its default input, used to
+// estimate the progress of days that don't report any.
+fn get_expected_durations(days: &[&Day]) -> HashMap<(u8, u8), Duration> {
+    let entries = history::read_entries().unwrap_or_default();
+    let mut expected_durations = HashMap::new();
+    for day in days {
+        let input_path = solver::get_day_directory(day).join("input.txt");
+        let Ok(input) = fs::read(input_path) else {
+            continue;
+        };
+        let input_hash = history::hash_input(&input);
+        for part in day.parts {
+            let durations: Vec<Duration> = entries
+                .iter()
+                .filter(|entry| {
+                    entry.day == day.number
+                        && entry.part == part.number
+                        && entry.input_hash == input_hash
+                })
+                .map(|entry| entry.elapsed)
+                .collect();
+            if let Some(median) = history::get_median(&durations) {
+                expected_durations.insert((day.number, part.number), median);
+            }
+        }
+    }
+    expected_durations
+}
+
+fn get_rows(
+    days: &[&Day],
+    statuses: &[DayStatus],
+    expected_durations: &HashMap<(u8, u8), Duration>,
+) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for (day, status) in days.iter().zip(statuses) {
+        for part in day.parts {
+            let answer = status
+                .answers
+                .iter()
+                .find(|answer| answer.part == part.number);
+            let mut row = Row {
+                day: day.number,
+                part: part.number,
+                state: "pending",
+                color: DIM,
+                progress: Some(0.0),
+                elapsed: None,
+                detail: String::new(),
+            };
+            if let Some(answer) = answer {
+                row.state = "done";
+                row.color = GREEN;
+                row.progress = Some(1.0);
+                row.elapsed = Some(answer.elapsed);
+                row.detail = answer.value.clone();
+            } else {
+                match &status.state {
+                    State::Pending => {}
+                    State::Building => {
+                        row.state = "building";
+                        row.color = YELLOW;
+                    }
+                    State::Running(started) => {
+                        let elapsed = started.elapsed();
+                        row.state = "running";
+                        row.color = YELLOW;
+                        row.elapsed = Some(elapsed);
+                        row.progress = status.reported_progress.or(expected_durations
+                            .get(&(day.number, part.number))
+                            .map(|expected| {
+                                (elapsed.as_secs_f64() / expected.as_secs_f64()).min(0.99)
+                            }));
+                    }
+                    State::Done => {
+                        row.state = "no answer";
+                        row.color = RED;
+                    }
+                    State::Failed(message) => {
+                        row.state = "failed";
+                        row.color = RED;
+                        row.detail = message.lines().next().unwrap_or_default().to_string();
+                    }
+                }
+            }
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+fn format_progress_bar(progress: Option<f64>) -> String {
+    match progress {
+        Some(progress) => {
+            let filled = (progress * BAR_WIDTH as f64).round() as usize;
+            format!(
+                "[{}{}] {:>3.0}%",
+                "#".repeat(filled),
+                ".".repeat(BAR_WIDTH - filled),
+                progress * 100.0
+            )
+        }
+        None => format!("[{}]    ?", "?".repeat(BAR_WIDTH)),
+    }
+}
+
+fn format_elapsed(elapsed: Option<Duration>) -> String {
+    elapsed
+        .map(|elapsed| format!("{:.2?}", elapsed))
+        .unwrap_or_default()
+}
+
+fn format_row(row: &Row) -> String {
+    format!(
+        "{}day{:<2} part {}  {:<9}  {}  {:>10}  {}{}",
+        row.color,
+        row.day,
+        row.part,
+        row.state,
+        format_progress_bar(row.progress),
+        format_elapsed(row.elapsed),
+        row.detail,
+        RESET
+    )
+}
+
+fn draw(rows: &[Row], is_redraw: bool) {
+    let mut text = String::new();
+    if is_redraw {
+        text.push_str(&format!("\x1b[{}A", rows.len()));
+    }
+    for row in rows {
+        text.push_str(CLEAR_LINE);
+        text.push_str(&format_row(row));
+        text.push('\n');
+    }
+    print!("{}", text);
+    let _ = io::stdout().flush();
+}
+
+// Without a terminal there's nothing to redraw, so every row is printed once
+// when it settles.
+fn print_settled_rows(rows: &[Row], printed_rows: &mut Vec<(u8, u8)>) {
+    for row in rows {
+        let is_settled = matches!(row.state, "done" | "failed" | "no answer");
+        if is_settled && !printed_rows.contains(&(row.day, row.part)) {
+            printed_rows.push((row.day, row.part));
+            let elapsed = format_elapsed(row.elapsed);
+            if elapsed.is_empty() {
+                println!(
+                    "day{} part {}: {} {}",
+                    row.day, row.part, row.state, row.detail
+                );
+            } else {
+                println!(
+                    "day{} part {}: {} {} ({})",
+                    row.day, row.part, row.state, row.detail, elapsed
+                );
+            }
+        }
+    }
+}
+
+fn run_day(day: &Day, status: &Mutex<DayStatus>) {
+    let set_state = |state: State| status.lock().unwrap().state = state;
+
+    set_state(State::Building);
+    let binary = match solver::build_day(day) {
+        Ok(binary) => binary,
+        Err(err) => return set_state(State::Failed(err)),
+    };
+
+    set_state(State::Running(Instant::now()));
+    let mut lines: Vec<TimedLine> = Vec::new();
+    let result = solver::run_built_day(day, &binary, None, |line| {
+        lines.push(TimedLine {
+            text: line.text.clone(),
+            elapsed: line.elapsed,
+        });
+        let mut status = status.lock().unwrap();
+        status.answers = solver::extract_answers(day, &lines);
+        if let Some(progress) = get_reported_progress(&line.text) {
+            status.reported_progress = Some(progress);
+        }
+    });
+
+    let mut status = status.lock().unwrap();
+    match result {
+        Ok(run) => {
+            status.answers = run.answers;
+            status.state = State::Done;
+        }
+        Err(err) => status.state = State::Failed(err),
+    }
+}
+
+pub fn run_days(days: &[&Day], jobs: usize) -> Result<(), String> {
+    let is_terminal = io::stdout().is_terminal();
+    let expected_durations = get_expected_durations(days);
+    let statuses: Vec<Mutex<DayStatus>> =
+        days.iter().map(|_| Mutex::new(DayStatus::new())).collect();
+    let next_day = AtomicUsize::new(0);
+    let finished_days = AtomicUsize::new(0);
+
+    if is_terminal {
+        print!("{}", HIDE_CURSOR);
+    }
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                let day_index = next_day.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(day_index) else {
+                    break;
+                };
+                run_day(day, &statuses[day_index]);
+                finished_days.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        let mut printed_rows = Vec::new();
+        let mut is_redraw = false;
+        loop {
+            let is_finished = finished_days.load(Ordering::Relaxed) == days.len();
+            let snapshot: Vec<DayStatus> = statuses
+                .iter()
+                .map(|status| status.lock().unwrap().clone())
+                .collect();
+            let rows = get_rows(days, &snapshot, &expected_durations);
+            if is_terminal {
+                draw(&rows, is_redraw);
+            } else {
+                print_settled_rows(&rows, &mut printed_rows);
+            }
+            is_redraw = true;
+            if is_finished {
+                break;
+            }
+            thread::sleep(REFRESH_INTERVAL);
+        }
+    });
+    if is_terminal {
+        print!("{}", SHOW_CURSOR);
+    }
+
+    let failed_days = statuses
+        .iter()
+        .filter(|status| matches!(status.lock().unwrap().state, State::Failed(_)))
+        .count();
+    match failed_days {
+        0 => Ok(()),
+        _ => Err(format!("{} days failed", failed_days)),
+    }
+}
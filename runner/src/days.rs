@@ -0,0 +1,101 @@
+pub struct Part {
+    pub number: u8,
+    pub answer_prefix: &'static str,
+}
+
+const fn part(number: u8, answer_prefix: &'static str) -> Part {
+    Part {
+        number,
+        answer_prefix,
+    }
+}
+
+pub struct Day {
+    pub number: u8,
+    pub parts: &'static [Part],
+}
+
+impl Day {
+    pub fn name(&self) -> String {
+        format!("day{}", self.number)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parts: &[
+            part(1, "Part one solution: "),
+            part(2, "Part two solution: "),
+        ],
+    },
+    Day {
+        number: 2,
+        parts: &[
+            part(1, "sum of ids: "),
+            part(2, "power of minimmum cubes: "),
+        ],
+    },
+    Day {
+        number: 3,
+        parts: &[
+            part(1, "Sum of the part numbers: "),
+            part(2, "Sum of the gear ratios: "),
+        ],
+    },
+    Day {
+        number: 4,
+        parts: &[part(1, "Total points: "), part(2, "Total cards: ")],
+    },
+    Day {
+        number: 5,
+        parts: &[
+            part(1, "Lowest location for part one: "),
+            part(2, "Lowest location for part two: "),
+        ],
+    },
+    Day {
+        number: 6,
+        parts: &[
+            part(1, "Part one solution: "),
+            part(2, "Part two solution: "),
+        ],
+    },
+    Day {
+        number: 7,
+        parts: &[part(2, "")],
+    },
+    Day {
+        number: 8,
+        parts: &[
+            part(1, "Part one solution: "),
+            part(2, "Part two solution: "),
+        ],
+    },
+    Day {
+        number: 9,
+        parts: &[part(1, "Part one: "), part(2, "Part two: ")],
+    },
+    Day {
+        number: 10,
+        parts: &[
+            part(1, "Steps to farthest pipe: "),
+            part(2, "Pipes on each side: "),
+        ],
+    },
+    Day {
+        number: 11,
+        parts: &[
+            part(1, "Sum of relative distances for part one: "),
+            part(2, "Sum of relative distances for part two: "),
+        ],
+    },
+    Day {
+        number: 12,
+        parts: &[part(1, "Sum of all possible arrangement counts: ")],
+    },
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,364 @@
+const NUMBER_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const CAMEL_CARDS: &str = "23456789TJQKA";
+const PIPE_GLYPHS: &str = "|-LJ7F.S";
+
+type Scorer = fn(&str, &[&str]) -> f64;
+
+#[derive(Debug)]
+pub struct Candidate {
+    pub day: u8,
+    pub score: f64,
+}
+
+#[derive(Debug)]
+pub struct Detection {
+    pub day: u8,
+    pub confidence: f64,
+    pub candidates: Vec<Candidate>,
+}
+
+pub fn detect_day(text: &str) -> Option<Detection> {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    let scorers: [(u8, Scorer); 12] = [
+        (1, score_calibration_document),
+        (2, score_cube_games),
+        (3, score_engine_schematic),
+        (4, score_scratchcards),
+        (5, score_almanac),
+        (6, score_race_sheet),
+        (7, score_camel_cards),
+        (8, score_desert_map),
+        (9, score_oasis_report),
+        (10, score_pipe_sketch),
+        (11, score_galaxy_image),
+        (12, score_spring_records),
+    ];
+
+    let mut candidates: Vec<Candidate> = scorers
+        .iter()
+        .map(|(day, scorer)| Candidate {
+            day: *day,
+            score: scorer(text, &lines),
+        })
+        .filter(|candidate| candidate.score > 0.0)
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.day.cmp(&b.day)));
+
+    let total_score: f64 = candidates.iter().map(|candidate| candidate.score).sum();
+    let best = candidates.first()?;
+
+    Some(Detection {
+        day: best.day,
+        confidence: best.score / total_score,
+        candidates,
+    })
+}
+
+fn fraction_of_lines(lines: &[&str], predicate: impl Fn(&str) -> bool) -> f64 {
+    let matching_lines = lines.iter().filter(|line| predicate(line)).count();
+    matching_lines as f64 / lines.len() as f64
+}
+
+fn is_grid(lines: &[&str]) -> bool {
+    let width = lines[0].chars().count();
+    lines.len() > 1 && width > 1 && lines.iter().all(|line| line.chars().count() == width)
+}
+
+fn is_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit())
+}
+
+fn is_comma_separated_list_of_numbers(text: &str) -> bool {
+    text.split(',').all(is_number)
+}
+
+fn is_labeled_line(line: &str, label: &str) -> bool {
+    match line.strip_prefix(label) {
+        Some(rest) => match rest.trim_start().split_once(':') {
+            Some((number, _)) => is_number(number),
+            None => false,
+        },
+        None => false,
+    }
+}
+
+fn score_calibration_document(_text: &str, lines: &[&str]) -> f64 {
+    fraction_of_lines(lines, |line| {
+        line.chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+            && (line.chars().any(|ch| ch.is_ascii_digit())
+                || NUMBER_WORDS.iter().any(|word| line.contains(word)))
+    })
+}
+
+fn score_cube_games(_text: &str, lines: &[&str]) -> f64 {
+    fraction_of_lines(lines, |line| is_labeled_line(line, "Game "))
+}
+
+fn score_engine_schematic(text: &str, lines: &[&str]) -> f64 {
+    if !is_grid(lines) || !text.chars().any(|ch| ch.is_ascii_digit()) {
+        return 0.0;
+    }
+    fraction_of_lines(lines, |line| {
+        line.chars()
+            .all(|ch| ch.is_ascii_digit() || ch.is_ascii_punctuation())
+    })
+}
+
+fn score_scratchcards(_text: &str, lines: &[&str]) -> f64 {
+    fraction_of_lines(lines, |line| {
+        is_labeled_line(line, "Card ") && line.contains('|')
+    })
+}
+
+fn score_almanac(text: &str, lines: &[&str]) -> f64 {
+    if !lines[0].starts_with("seeds:") || !text.contains("seed-to-soil map:") {
+        return 0.0;
+    }
+    fraction_of_lines(lines, |line| {
+        let values: Vec<&str> = line.split_whitespace().collect();
+        line.starts_with("seeds:")
+            || line.ends_with(" map:")
+            || (values.len() == 3 && values.iter().all(|value| is_number(value)))
+    })
+}
+
+fn score_race_sheet(text: &str, lines: &[&str]) -> f64 {
+    if !text.contains("Time:") || !text.contains("Distance:") {
+        return 0.0;
+    }
+    fraction_of_lines(lines, |line| {
+        line.starts_with("Time:") || line.starts_with("Distance:")
+    })
+}
+
+fn score_camel_cards(_text: &str, lines: &[&str]) -> f64 {
+    fraction_of_lines(lines, |line| match line.split_once(' ') {
+        Some((cards, bid)) => {
+            cards.chars().count() == 5
+                && cards.chars().all(|card| CAMEL_CARDS.contains(card))
+                && is_number(bid)
+        }
+        None => false,
+    })
+}
+
+fn is_desert_node(line: &str) -> bool {
+    match line.split_once(" = ") {
+        Some((node, destinations)) => {
+            let destinations = destinations
+                .strip_prefix('(')
+                .and_then(|destinations| destinations.strip_suffix(')'))
+                .and_then(|destinations| destinations.split_once(", "));
+            !node.is_empty()
+                && node.chars().all(|ch| ch.is_ascii_alphanumeric())
+                && destinations.is_some()
+        }
+        None => false,
+    }
+}
+
+fn score_desert_map(_text: &str, lines: &[&str]) -> f64 {
+    let instructions = lines[0];
+    if !instructions.chars().all(|ch| ch == 'L' || ch == 'R') {
+        return 0.0;
+    }
+    fraction_of_lines(lines, |line| line == instructions || is_desert_node(line))
+}
+
+fn score_oasis_report(text: &str, lines: &[&str]) -> f64 {
+    if text.contains(':') {
+        return 0.0;
+    }
+    fraction_of_lines(lines, |line| {
+        let values: Vec<&str> = line.split_whitespace().collect();
+        values.len() > 1 && values.iter().all(|value| is_number(value))
+    })
+}
+
+fn score_pipe_sketch(text: &str, lines: &[&str]) -> f64 {
+    if !is_grid(lines) {
+        return 0.0;
+    }
+    let score = fraction_of_lines(lines, |line| {
+        line.chars().all(|ch| PIPE_GLYPHS.contains(ch))
+    });
+    match text.matches('S').count() {
+        1 => score,
+        _ => score / 2.0,
+    }
+}
+
+fn score_galaxy_image(text: &str, lines: &[&str]) -> f64 {
+    if !is_grid(lines) || !text.contains('#') {
+        return 0.0;
+    }
+    fraction_of_lines(lines, |line| line.chars().all(|ch| ch == '.' || ch == '#'))
+}
+
+fn score_spring_records(_text: &str, lines: &[&str]) -> f64 {
+    fraction_of_lines(lines, |line| match line.split_once(' ') {
+        Some((conditions, lengths)) => {
+            !conditions.is_empty()
+                && conditions
+                    .chars()
+                    .all(|ch| ch == '.' || ch == '#' || ch == '?')
+                && is_comma_separated_list_of_numbers(lengths)
+        }
+        None => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_score(detection: &Detection, day: u8) -> f64 {
+        detection
+            .candidates
+            .iter()
+            .find(|candidate| candidate.day == day)
+            .map(|candidate| candidate.score)
+            .unwrap_or(0.0)
+    }
+
+    fn assert_detects(text: &str, day: u8) {
+        let detection = detect_day(text).unwrap();
+        assert_eq!(detection.day, day, "{:?}", detection.candidates);
+        assert_eq!(detection.confidence, 1.0, "{:?}", detection.candidates);
+    }
+
+    #[test]
+    fn detects_day1() {
+        assert_detects("two1nine\neightwothree\nabcone2threexyz\n", 1);
+    }
+
+    #[test]
+    fn detects_day2() {
+        assert_detects(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+             Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n",
+            2,
+        );
+    }
+
+    #[test]
+    fn detects_day3() {
+        assert_detects("467..114..\n...*......\n..35..633.\n617*......\n", 3);
+    }
+
+    #[test]
+    fn detects_day4() {
+        assert_detects(
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+             Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n",
+            4,
+        );
+    }
+
+    #[test]
+    fn detects_day5() {
+        assert_detects(
+            "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\n\
+             soil-to-fertilizer map:\n0 15 37\n",
+            5,
+        );
+    }
+
+    #[test]
+    fn detects_day6() {
+        assert_detects("Time:      7  15   30\nDistance:  9  40  200\n", 6);
+    }
+
+    #[test]
+    fn detects_day7() {
+        assert_detects("32T3K 765\nT55J5 684\nKK677 28\n", 7);
+    }
+
+    #[test]
+    fn detects_day8() {
+        assert_detects(
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+            8,
+        );
+    }
+
+    #[test]
+    fn detects_day9() {
+        assert_detects("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 -45\n", 9);
+    }
+
+    #[test]
+    fn detects_day10() {
+        assert_detects("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...\n", 10);
+    }
+
+    #[test]
+    fn detects_day11() {
+        assert_detects("...#......\n.......#..\n#.........\n", 11);
+    }
+
+    #[test]
+    fn detects_day12() {
+        assert_detects(
+            "???.### 1,1,3\n.??..??...?##. 1,1,3\n?###???????? 3,2,1\n",
+            12,
+        );
+    }
+
+    #[test]
+    fn nothing_to_detect_in_blank_input() {
+        assert!(detect_day("").is_none());
+        assert!(detect_day("\n  \n").is_none());
+        assert!(detect_day("Hello, World!\n").is_none());
+    }
+
+    #[test]
+    fn a_grid_of_dots_and_hashes_scores_for_galaxies_and_pipes() {
+        // A row of only '.' is also a pipe row, but without an 'S' the pipe
+        // score is halved. Spring records need their group lengths.
+        let detection = detect_day("#.#.\n.##.\n....\n").unwrap();
+        assert_eq!(detection.day, 11);
+        assert_eq!(get_score(&detection, 11), 1.0);
+        assert_eq!(get_score(&detection, 10), 1.0 / 6.0);
+        assert_eq!(get_score(&detection, 12), 0.0);
+        assert_eq!(detection.confidence, 1.0 / (1.0 + 1.0 / 6.0));
+    }
+
+    #[test]
+    fn pipe_grids_without_exactly_one_start_are_halved() {
+        let detection = detect_day("..F7.\n.FJ|.\n.J.L7\n").unwrap();
+        assert_eq!(get_score(&detection, 10), 0.5);
+
+        let detection = detect_day("..F7.\n.FJ|S\nSJ.L7\n").unwrap();
+        assert_eq!(get_score(&detection, 10), 0.5);
+    }
+
+    #[test]
+    fn ties_split_the_confidence_and_go_to_the_earlier_day() {
+        // Digit-only lines read as both calibration lines and a schematic.
+        let detection = detect_day("1234\n5678\n").unwrap();
+        assert_eq!(detection.day, 1);
+        assert_eq!(detection.confidence, 0.5);
+        assert_eq!(get_score(&detection, 3), 1.0);
+    }
+
+    #[test]
+    fn partial_matches_lower_the_confidence() {
+        // Only one of the three lines reads as a calibration line.
+        let detection = detect_day("two1nine\n32T3K 765\nT55J5 684\n").unwrap();
+        assert_eq!(detection.day, 7);
+        assert!(detection.confidence < 1.0);
+        assert_eq!(get_score(&detection, 1), 1.0 / 3.0);
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::solver;
+
+const HISTORY_FILE_NAME: &str = "performance_history.tsv";
+const HEADER: &str = "timestamp\tday\tpart\tinput_hash\tcommit\tnanoseconds";
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    pub commit: String,
+    pub elapsed: Duration,
+}
+
+impl Entry {
+    pub fn new(day: u8, part: u8, input_hash: &str, commit: &str, elapsed: Duration) -> Entry {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        Entry {
+            timestamp,
+            day,
+            part,
+            input_hash: input_hash.to_string(),
+            commit: commit.to_string(),
+            elapsed,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.input_hash,
+            self.commit,
+            self.elapsed.as_nanos()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Entry> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 6 {
+            return None;
+        }
+        Some(Entry {
+            timestamp: fields[0].parse().ok()?,
+            day: fields[1].parse().ok()?,
+            part: fields[2].parse().ok()?,
+            input_hash: fields[3].to_string(),
+            commit: fields[4].to_string(),
+            elapsed: Duration::from_nanos(fields[5].parse().ok()?),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    pub commit: String,
+    pub latest: Duration,
+    pub median: Duration,
+    pub is_regression: bool,
+}
+
+pub fn get_history_path() -> PathBuf {
+    solver::get_repository_directory().join(HISTORY_FILE_NAME)
+}
+
+// FNV-1a, so the same input hashes the same on every machine and toolchain.
+pub fn hash_input(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+pub fn get_git_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(solver::get_repository_directory())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or("unknown".to_string())
+}
+
+pub fn append_entries(entries: &[Entry]) -> Result<(), String> {
+    let history_path = get_history_path();
+    let is_new_file = !history_path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .map_err(|err| format!("Couldn't open {}: {}", history_path.display(), err))?;
+
+    let mut text = String::new();
+    if is_new_file {
+        text.push_str(HEADER);
+        text.push('\n');
+    }
+    for entry in entries {
+        text.push_str(&entry.to_line());
+        text.push('\n');
+    }
+    file.write_all(text.as_bytes())
+        .map_err(|err| format!("Couldn't write {}: {}", history_path.display(), err))
+}
+
+pub fn read_entries() -> Result<Vec<Entry>, String> {
+    let history_path = get_history_path();
+    let text = fs::read_to_string(&history_path)
+        .map_err(|err| format!("Couldn't read {}: {}", history_path.display(), err))?;
+    Ok(text.lines().filter_map(Entry::from_line).collect())
+}
+
+// None for an empty slice.
+pub fn get_median(durations: &[Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    let mut durations = durations.to_vec();
+    durations.sort();
+    let middle = durations.len() / 2;
+    match durations.len() % 2 {
+        0 => Some((durations[middle - 1] + durations[middle]) / 2),
+        _ => Some(durations[middle]),
+    }
+}
+
+// The latest run of every day, part and input is compared against the
+// median of up to `window` runs before it.
+pub fn compare_latest_runs(entries: &[Entry], window: usize, threshold: f64) -> Vec<Comparison> {
+    let mut groups: BTreeMap<(u8, u8, String), Vec<&Entry>> = BTreeMap::new();
+    for entry in entries {
+        groups
+            .entry((entry.day, entry.part, entry.input_hash.clone()))
+            .or_default()
+            .push(entry);
+    }
+
+    let mut comparisons = Vec::new();
+    for ((day, part, input_hash), group) in groups {
+        let Some((latest, previous)) = group.split_last() else {
+            continue;
+        };
+        if previous.is_empty() {
+            continue;
+        }
+        let recent: Vec<Duration> = previous
+            .iter()
+            .rev()
+            .take(window)
+            .map(|entry| entry.elapsed)
+            .collect();
+        let Some(median) = get_median(&recent) else {
+            continue;
+        };
+        comparisons.push(Comparison {
+            day,
+            part,
+            input_hash,
+            commit: latest.commit.clone(),
+            latest: latest.elapsed,
+            median,
+            is_regression: latest.elapsed.as_secs_f64() > median.as_secs_f64() * (1.0 + threshold),
+        });
+    }
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_entries(day: u8, milliseconds: &[u64]) -> Vec<Entry> {
+        milliseconds
+            .iter()
+            .map(|&ms| Entry::new(day, 1, "hash", "commit", Duration::from_millis(ms)))
+            .collect()
+    }
+
+    fn get_only_comparison(entries: &[Entry], window: usize) -> Comparison {
+        let mut comparisons = compare_latest_runs(entries, window, 0.2);
+        assert_eq!(comparisons.len(), 1);
+        comparisons.remove(0)
+    }
+
+    #[test]
+    fn median_of_nothing_is_none() {
+        assert_eq!(get_median(&[]), None);
+    }
+
+    #[test]
+    fn window_zero_compares_nothing() {
+        let entries = get_entries(9, &[10, 20]);
+        assert!(compare_latest_runs(&entries, 0, 0.2).is_empty());
+    }
+
+    #[test]
+    fn window_one_uses_the_run_before_the_latest() {
+        let comparison = get_only_comparison(&get_entries(9, &[100, 10, 13]), 1);
+        assert_eq!(comparison.median, Duration::from_millis(10));
+        assert_eq!(comparison.latest, Duration::from_millis(13));
+        assert!(comparison.is_regression);
+    }
+
+    #[test]
+    fn odd_window_uses_the_middle_run() {
+        let comparison = get_only_comparison(&get_entries(9, &[30, 10, 20, 21]), 3);
+        assert_eq!(comparison.median, Duration::from_millis(20));
+        assert!(!comparison.is_regression);
+    }
+
+    #[test]
+    fn even_window_averages_the_middle_runs() {
+        let comparison = get_only_comparison(&get_entries(9, &[1000, 40, 10, 30, 20, 31]), 4);
+        assert_eq!(comparison.median, Duration::from_millis(25));
+        assert!(comparison.is_regression);
+    }
+
+    #[test]
+    fn a_single_run_has_nothing_to_compare_against() {
+        let mut entries = get_entries(9, &[10]);
+        entries.extend(get_entries(10, &[10, 10]));
+        let comparison = get_only_comparison(&entries, 5);
+        assert_eq!(comparison.day, 10);
+        assert!(!comparison.is_regression);
+    }
+}
@@ -0,0 +1,365 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+use common::cli::Args;
+
+mod dashboard;
+mod days;
+mod detect;
+mod history;
+mod minimize;
+mod plugins;
+mod random;
+mod report;
+mod robustness;
+mod scaffold;
+mod solver;
+mod statistics;
+
+const USAGE: &str = "Usage:
+    runner solve <day> [input file] [--impl builtin|plugin:NAME] [--plugin-dir DIR]
+    runner plugins [--plugin-dir DIR]
+    runner detect <input file> [--solve]
+    runner robustness [days...] [--seed N] [--iterations N] [--timeout-ms N]
+    runner new-day <day>
+    runner bench [days...] [--input FILE] [--runs N]
+    runner compare [days...] [--threshold FRACTION] [--window N]
+    runner dashboard [days...] [--jobs N]
+    runner report [days...] [--output FILE]
+    runner minimize <day> <input file> --predicate panic|mismatch|slow [--output FILE]
+        [--impl IMPL] [--reference IMPL] [--budget-ms N] [--timeout-ms N] [--plugin-dir DIR]";
+const OPTIONS_WITH_VALUES: [&str; 14] = [
+    "--impl",
+    "--plugin-dir",
+    "--seed",
+    "--iterations",
+    "--timeout-ms",
+    "--input",
+    "--runs",
+    "--threshold",
+    "--window",
+    "--jobs",
+    "--output",
+    "--predicate",
+    "--reference",
+    "--budget-ms",
+];
+// Advent of Code has 25 puzzles a year.
+const LAST_DAY: u8 = 25;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let args = Args::from(args.collect(), &OPTIONS_WITH_VALUES);
+    let result = match command.as_deref() {
+        Some("solve") => solve(&args),
+        Some("detect") => detect(&args),
+        Some("robustness") => check_robustness(&args),
+        Some("new-day") => new_day(&args),
+        Some("bench") => bench(&args),
+        Some("compare") => compare(&args),
+        Some("plugins") => list_plugins(&args),
+        Some("dashboard") => show_dashboard(&args),
+        Some("report") => write_report(&args),
+        Some("minimize") => minimize_input(&args),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn parse_days(args: &Args) -> Result<Vec<&'static days::Day>, String> {
+    let positionals = args.get_positionals();
+    if positionals.is_empty() {
+        return Ok(days::DAYS.iter().collect());
+    }
+    positionals.into_iter().map(parse_day).collect()
+}
+
+fn parse_day_number(text: &str) -> Result<u8, String> {
+    text.trim_start_matches("day")
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", text))
+}
+
+fn parse_day(text: &str) -> Result<&'static days::Day, String> {
+    let number = parse_day_number(text)?;
+    days::find_day(number).ok_or(format!("Unknown day: {}", number))
+}
+
+fn get_plugin_directory(args: &Args) -> PathBuf {
+    args.get_option("--plugin-dir")
+        .map(PathBuf::from)
+        .unwrap_or_else(plugins::get_default_plugin_directory)
+}
+
+fn solve(args: &Args) -> Result<(), String> {
+    let positionals = args.get_positionals();
+    let day = parse_day(positionals.first().ok_or(USAGE)?)?;
+    let input_path = positionals.get(1).map(Path::new);
+    let implementation =
+        solver::Implementation::from(args.get_option("--impl").unwrap_or("builtin"))?;
+
+    let run = solver::run_implementation(
+        day,
+        &implementation,
+        input_path,
+        &get_plugin_directory(args),
+    )?;
+    print_answers(day, &run);
+    Ok(())
+}
+
+fn list_plugins(args: &Args) -> Result<(), String> {
+    let plugin_directory = get_plugin_directory(args);
+    let solvers = plugins::load_plugins(&plugin_directory)?;
+    if solvers.is_empty() {
+        println!("No plugins found in {}", plugin_directory.display());
+    }
+    for solver in solvers {
+        println!(
+            "plugin:{} solves day{} part {} ({})",
+            solver.name,
+            solver.day,
+            solver.part,
+            solver.path.display()
+        );
+    }
+    Ok(())
+}
+
+fn detect(args: &Args) -> Result<(), String> {
+    let input_path = Path::new(*args.get_positionals().first().ok_or(USAGE)?);
+    let should_solve = args.has_flag("--solve");
+
+    let text = fs::read_to_string(input_path)
+        .map_err(|err| format!("Couldn't read {}: {}", input_path.display(), err))?;
+    let detection = detect::detect_day(&text).ok_or("Couldn't recognize the input file")?;
+
+    println!(
+        "Most likely day{} (confidence {:.0}%)",
+        detection.day,
+        detection.confidence * 100.0
+    );
+    for candidate in &detection.candidates {
+        println!("    day{}: {:.2}", candidate.day, candidate.score);
+    }
+
+    if should_solve {
+        let day = days::find_day(detection.day).ok_or("Detected day isn't registered")?;
+        print_run(day, Some(input_path))?;
+    }
+    Ok(())
+}
+
+fn print_answers(day: &days::Day, run: &solver::Run) {
+    for answer in &run.answers {
+        println!("{} part {}: {}", day.name(), answer.part, answer.value);
+    }
+    println!("Calculated in {:.2?}", run.elapsed);
+}
+
+fn print_run(day: &days::Day, input_path: Option<&Path>) -> Result<(), String> {
+    let run = solver::run_day(day, input_path)?;
+    print_answers(day, &run);
+    Ok(())
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let runs = args.parse_option("--runs", 1)?;
+    let input_path = args.get_option("--input").map(Path::new);
+    let days = parse_days(args)?;
+    if input_path.is_some() && days.len() != 1 {
+        return Err("--input needs exactly one day".to_string());
+    }
+
+    let commit = history::get_git_commit();
+    for day in days {
+        let default_input_path = solver::get_day_directory(day).join("input.txt");
+        let hashed_input_path = input_path.unwrap_or(&default_input_path);
+        let input = fs::read(hashed_input_path)
+            .map_err(|err| format!("Couldn't read {}: {}", hashed_input_path.display(), err))?;
+        let input_hash = history::hash_input(&input);
+
+        let mut entries = Vec::new();
+        for _ in 0..runs {
+            let run = solver::run_day(day, input_path)?;
+            for answer in &run.answers {
+                println!(
+                    "{} part {}: {:.2?}",
+                    day.name(),
+                    answer.part,
+                    answer.elapsed
+                );
+                entries.push(history::Entry::new(
+                    day.number,
+                    answer.part,
+                    &input_hash,
+                    &commit,
+                    answer.elapsed,
+                ));
+            }
+        }
+        history::append_entries(&entries)?;
+    }
+    println!("Recorded in {}", history::get_history_path().display());
+    Ok(())
+}
+
+fn compare(args: &Args) -> Result<(), String> {
+    let threshold: f64 = args.parse_option("--threshold", 0.2)?;
+    if threshold < 0.0 {
+        return Err("--threshold can't be negative".to_string());
+    }
+    let window = args.parse_option("--window", 5)?;
+    if window == 0 {
+        return Err("--window must be at least 1".to_string());
+    }
+    let days: Vec<u8> = parse_days(args)?.iter().map(|day| day.number).collect();
+
+    let entries: Vec<history::Entry> = history::read_entries()?
+        .into_iter()
+        .filter(|entry| days.contains(&entry.day))
+        .collect();
+
+    let mut regression_count = 0;
+    for comparison in history::compare_latest_runs(&entries, window, threshold) {
+        let change = comparison.latest.as_secs_f64() / comparison.median.as_secs_f64() - 1.0;
+        println!(
+            "day{} part {} (input {}, commit {}): {:.2?} against a median of {:.2?} ({:+.0}%){}",
+            comparison.day,
+            comparison.part,
+            comparison.input_hash,
+            comparison.commit,
+            comparison.latest,
+            comparison.median,
+            change * 100.0,
+            if comparison.is_regression {
+                " SLOWER"
+            } else {
+                ""
+            }
+        );
+        if comparison.is_regression {
+            regression_count += 1;
+        }
+    }
+
+    match regression_count {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} solvers got more than {:.0}% slower than their recent median",
+            regression_count,
+            threshold * 100.0
+        )),
+    }
+}
+
+fn show_dashboard(args: &Args) -> Result<(), String> {
+    let default_jobs = std::thread::available_parallelism()
+        .map(|jobs| jobs.get())
+        .unwrap_or(1);
+    let jobs = args.parse_option("--jobs", default_jobs)?;
+    dashboard::run_days(&parse_days(args)?, jobs)
+}
+
+fn write_report(args: &Args) -> Result<(), String> {
+    let output_path = Path::new(args.get_option("--output").unwrap_or("report.md"));
+    let report = report::create_report(&parse_days(args)?);
+    fs::write(output_path, report)
+        .map_err(|err| format!("Couldn't write {}: {}", output_path.display(), err))?;
+    println!("Wrote {}", output_path.display());
+    Ok(())
+}
+
+fn minimize_input(args: &Args) -> Result<(), String> {
+    let positionals = args.get_positionals();
+    let (Some(day), Some(input_path)) = (positionals.first(), positionals.get(1)) else {
+        return Err(USAGE.to_string());
+    };
+    let day = parse_day(day)?;
+    let input_path = Path::new(input_path);
+    let output_path = Path::new(args.get_option("--output").unwrap_or("minimized.txt"));
+    let timeout = Duration::from_millis(args.parse_option("--timeout-ms", 10000)?);
+
+    let predicate = match args.get_option("--predicate").ok_or(USAGE)? {
+        "panic" => minimize::Predicate::Panic,
+        "slow" => minimize::Predicate::Slow(Duration::from_millis(
+            args.parse_option("--budget-ms", 1000)?,
+        )),
+        "mismatch" => {
+            let reference = args
+                .get_option("--reference")
+                .ok_or("The mismatch predicate needs a --reference implementation")?;
+            minimize::Predicate::Mismatch(
+                solver::Implementation::from(args.get_option("--impl").unwrap_or("builtin"))?,
+                solver::Implementation::from(reference)?,
+            )
+        }
+        predicate => return Err(format!("Unknown predicate: {}", predicate)),
+    };
+
+    let minimization = minimize::minimize(
+        day,
+        input_path,
+        &predicate,
+        &get_plugin_directory(args),
+        timeout,
+    )?;
+    fs::write(output_path, &minimization.text)
+        .map_err(|err| format!("Couldn't write {}: {}", output_path.display(), err))?;
+
+    if let Some(panic_location) = &minimization.panic_location {
+        println!("Panics at {}", panic_location);
+    }
+    println!(
+        "Reduced to {} lines and {} bytes in {} attempts, written to {}",
+        minimization.text.lines().count(),
+        minimization.text.len(),
+        minimization.attempts,
+        output_path.display()
+    );
+    Ok(())
+}
+
+fn check_robustness(args: &Args) -> Result<(), String> {
+    let seed = args.parse_option("--seed", 2023)?;
+    let iterations = args.parse_option("--iterations", 200)?;
+    let timeout = Duration::from_millis(args.parse_option("--timeout-ms", 5000)?);
+
+    let mut random = random::Random::new(seed);
+    let mut crash_count = 0;
+    for day in parse_days(args)? {
+        let report = robustness::check_day(day, iterations, timeout, &mut random)?;
+        println!(
+            "{}: {} answered, {} rejected, {} timed out, {} crashed",
+            day.name(),
+            report.answered,
+            report.rejected,
+            report.timed_out,
+            report.crashes.len()
+        );
+        for crash in &report.crashes {
+            println!("    {} ({})", crash.message, crash.input_path.display());
+        }
+        crash_count += report.crashes.len();
+    }
+
+    match crash_count {
+        0 => Ok(()),
+        _ => Err(format!("{} mutated inputs crashed a solver", crash_count)),
+    }
+}
+
+fn new_day(args: &Args) -> Result<(), String> {
+    let number = parse_day_number(args.get_positionals().first().ok_or(USAGE)?)?;
+    if !(1..=LAST_DAY).contains(&number) {
+        return Err(format!("Days go from 1 to {}, not {}", LAST_DAY, number));
+    }
+    scaffold::create_day(number)
+}
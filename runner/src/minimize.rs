@@ -0,0 +1,270 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::days::Day;
+use crate::plugins;
+use crate::robustness::{self, Outcome};
+use crate::solver::{self, Implementation};
+
+const TOKEN_SEPARATORS: &[char] = &[' ', ',', ';', ':', '='];
+
+#[derive(Debug)]
+pub enum Predicate {
+    Panic,
+    Mismatch(Implementation, Implementation),
+    Slow(Duration),
+}
+
+struct Checker<'a> {
+    day: &'a Day,
+    predicate: &'a Predicate,
+    binary: PathBuf,
+    candidate_path: PathBuf,
+    plugin_directory: &'a Path,
+    timeout: Duration,
+    attempts: usize,
+    // Set by the original input, so the reduction can't drift to another panic.
+    panic_location: Option<String>,
+}
+
+fn get_panic_location(message: &str) -> String {
+    match message.split_once("panicked at ") {
+        Some((_, rest)) => rest
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(':')
+            .to_string(),
+        None => message.to_string(),
+    }
+}
+
+impl Checker<'_> {
+    fn run_candidate(&self, timeout: Duration) -> Result<Outcome, String> {
+        let mut command =
+            solver::get_day_command(self.day, &self.binary, Some(&self.candidate_path))?;
+        Ok(robustness::classify(solver::run_command_with_timeout(
+            &mut command,
+            timeout,
+        )?))
+    }
+
+    // None when the implementation fails or runs past the timeout.
+    fn run_answers(&self, implementation: &Implementation) -> Option<Vec<(u8, String)>> {
+        let run = match implementation {
+            Implementation::Builtin => solver::run_built_day_with_timeout(
+                self.day,
+                &self.binary,
+                &self.candidate_path,
+                self.timeout,
+            ),
+            Implementation::Plugin(name) => plugins::run_plugin_with_timeout(
+                self.day,
+                name,
+                &self.candidate_path,
+                self.plugin_directory,
+                self.timeout,
+            ),
+        };
+        let run = run.ok()??;
+        Some(
+            run.answers
+                .into_iter()
+                .map(|answer| (answer.part, answer.value))
+                .collect(),
+        )
+    }
+
+    fn is_mismatch(&self, implementation: &Implementation, reference: &Implementation) -> bool {
+        let (Some(answers), Some(reference_answers)) = (
+            self.run_answers(implementation),
+            self.run_answers(reference),
+        ) else {
+            return false;
+        };
+        answers.iter().any(|(part, value)| {
+            reference_answers
+                .iter()
+                .any(|(reference_part, reference_value)| {
+                    part == reference_part && value != reference_value
+                })
+        })
+    }
+
+    fn holds(&mut self, text: &str) -> Result<bool, String> {
+        self.attempts += 1;
+        fs::write(&self.candidate_path, text)
+            .map_err(|err| format!("Couldn't write {}: {}", self.candidate_path.display(), err))?;
+
+        Ok(match self.predicate {
+            Predicate::Panic => {
+                let Outcome::Crashed(message) = self.run_candidate(self.timeout)? else {
+                    return Ok(false);
+                };
+                let location = get_panic_location(&message);
+                match &self.panic_location {
+                    Some(panic_location) => location == *panic_location,
+                    None => {
+                        self.panic_location = Some(location);
+                        true
+                    }
+                }
+            }
+            Predicate::Slow(budget) => matches!(self.run_candidate(*budget)?, Outcome::TimedOut),
+            Predicate::Mismatch(implementation, reference) => {
+                self.is_mismatch(implementation, reference)
+            }
+        })
+    }
+}
+
+fn join_lines(lines: &[String]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+// Delta debugging: removes ever smaller chunks of `items` for as long as
+// the rest still satisfies `holds`, down to single items.
+fn reduce<T: Clone>(
+    items: Vec<T>,
+    holds: &mut impl FnMut(&[T]) -> Result<bool, String>,
+) -> Result<Vec<T>, String> {
+    let mut items = items;
+    let mut chunk_size = items.len().div_ceil(2);
+    while chunk_size > 0 && !items.is_empty() {
+        let mut start = 0;
+        let mut has_removed = false;
+        while start < items.len() {
+            let end = (start + chunk_size).min(items.len());
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[end..]);
+            if holds(&candidate)? {
+                items = candidate;
+                has_removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !has_removed {
+            chunk_size /= 2;
+        }
+    }
+    Ok(items)
+}
+
+fn reduce_lines(lines: Vec<String>, checker: &mut Checker) -> Result<Vec<String>, String> {
+    reduce(lines, &mut |candidate| {
+        checker.holds(&join_lines(candidate))
+    })
+}
+
+// Only grids have columns, so ragged inputs are left alone.
+fn reduce_columns(lines: Vec<String>, checker: &mut Checker) -> Result<Vec<String>, String> {
+    let rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+    let width = rows.first().map(Vec::len).unwrap_or(0);
+    if width < 2 || rows.iter().any(|row| row.len() != width) {
+        return Ok(lines);
+    }
+
+    let get_lines = |columns: &[usize]| -> Vec<String> {
+        rows.iter()
+            .map(|row| columns.iter().map(|&column| row[column]).collect())
+            .collect()
+    };
+    let columns = reduce((0..width).collect(), &mut |columns| {
+        checker.holds(&join_lines(&get_lines(columns)))
+    })?;
+    Ok(get_lines(&columns))
+}
+
+// A token is a run of characters together with the separators after it.
+fn split_tokens(line: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut token = String::new();
+    let mut is_in_separators = false;
+    for ch in line.chars() {
+        let is_separator = TOKEN_SEPARATORS.contains(&ch);
+        if is_in_separators && !is_separator {
+            tokens.push(std::mem::take(&mut token));
+        }
+        is_in_separators = is_separator;
+        token.push(ch);
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn reduce_tokens(lines: Vec<String>, checker: &mut Checker) -> Result<Vec<String>, String> {
+    let mut lines = lines;
+    for i in 0..lines.len() {
+        let tokens = split_tokens(&lines[i]);
+        if tokens.len() < 2 {
+            continue;
+        }
+        let tokens = reduce(tokens, &mut |tokens| {
+            let mut candidate = lines.clone();
+            candidate[i] = tokens.concat();
+            checker.holds(&join_lines(&candidate))
+        })?;
+        lines[i] = tokens.concat();
+    }
+    Ok(lines)
+}
+
+#[derive(Debug)]
+pub struct Minimization {
+    pub text: String,
+    pub attempts: usize,
+    pub panic_location: Option<String>,
+}
+
+pub fn minimize(
+    day: &Day,
+    input_path: &Path,
+    predicate: &Predicate,
+    plugin_directory: &Path,
+    timeout: Duration,
+) -> Result<Minimization, String> {
+    let text = fs::read_to_string(input_path)
+        .map_err(|err| format!("Couldn't read {}: {}", input_path.display(), err))?;
+    let work_directory = solver::get_repository_directory()
+        .join("runner")
+        .join("target")
+        .join("minimize");
+    fs::create_dir_all(&work_directory).map_err(|err| err.to_string())?;
+
+    let mut checker = Checker {
+        day,
+        predicate,
+        binary: solver::build_day(day)?,
+        candidate_path: work_directory.join(format!("{}-candidate.txt", day.name())),
+        plugin_directory,
+        timeout,
+        attempts: 0,
+        panic_location: None,
+    };
+
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    if !checker.holds(&join_lines(&lines))? {
+        return Err("The predicate doesn't hold for the original input".to_string());
+    }
+
+    loop {
+        let before = join_lines(&lines);
+        lines = reduce_lines(lines, &mut checker)?;
+        lines = reduce_columns(lines, &mut checker)?;
+        lines = reduce_tokens(lines, &mut checker)?;
+        if join_lines(&lines) == before {
+            break;
+        }
+    }
+
+    let _ = fs::remove_file(&checker.candidate_path);
+    Ok(Minimization {
+        text: join_lines(&lines),
+        attempts: checker.attempts,
+        panic_location: checker.panic_location,
+    })
+}
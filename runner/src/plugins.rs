@@ -0,0 +1,345 @@
+use std::env;
+use std::env::consts::DLL_EXTENSION;
+use std::ffi::{c_char, CStr};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(unix)]
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+use crate::solver::{self, Answer, Run};
+
+const UNSUPPORTED_PLATFORM: &str = "Plugins are unsupported on this platform";
+
+// A plugin exports these two functions:
+//
+//     void aoc_plugin_register(void *context, RegisterFn register);
+//     void aoc_plugin_free(char *answer);
+//
+// and calls `register` once for every day and part it solves. `solve`
+// receives the input text and returns a NUL-terminated answer that the
+// runner hands back to `aoc_plugin_free`, or NULL when it can't solve it.
+pub type SolveFn = unsafe extern "C" fn(input: *const u8, input_len: usize) -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(answer: *mut c_char);
+
+// Only the Unix loader builds these.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct PluginSolver {
+    pub name: String,
+    pub day: u8,
+    pub part: u8,
+    pub path: PathBuf,
+    solve: SolveFn,
+    free: FreeFn,
+    // Keeps the library loaded for as long as its functions can be called.
+    #[cfg(unix)]
+    _library: Rc<loader::Library>,
+}
+
+impl PluginSolver {
+    pub fn solve(&self, input: &str) -> Result<String, String> {
+        let answer = unsafe { (self.solve)(input.as_ptr(), input.len()) };
+        if answer.is_null() {
+            return Err(format!(
+                "Plugin {} couldn't solve day{} part {}",
+                self.name, self.day, self.part
+            ));
+        }
+        let text = unsafe { CStr::from_ptr(answer) }
+            .to_string_lossy()
+            .to_string();
+        unsafe { (self.free)(answer) };
+        Ok(text)
+    }
+}
+
+// dlopen only exists on Unix. Elsewhere the runner still builds, but loading
+// a plugin fails.
+#[cfg(unix)]
+mod loader {
+    use std::ffi::{c_char, c_int, c_void, CStr, CString};
+    use std::path::Path;
+    use std::rc::Rc;
+
+    use super::{FreeFn, PluginSolver, SolveFn};
+
+    const RTLD_NOW: c_int = 2;
+    const REGISTER_SYMBOL: &str = "aoc_plugin_register";
+    const FREE_SYMBOL: &str = "aoc_plugin_free";
+
+    type RegisterFn = unsafe extern "C" fn(
+        context: *mut c_void,
+        day: u32,
+        part: u32,
+        name: *const c_char,
+        solve: SolveFn,
+    );
+    type PluginRegisterFn = unsafe extern "C" fn(context: *mut c_void, register: RegisterFn);
+
+    #[cfg_attr(target_os = "linux", link(name = "dl"))]
+    extern "C" {
+        fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        fn dlerror() -> *mut c_char;
+        fn dlclose(handle: *mut c_void) -> c_int;
+    }
+
+    pub struct Library {
+        handle: *mut c_void,
+    }
+
+    impl Library {
+        fn open(path: &Path) -> Result<Library, String> {
+            let path_text = CString::new(path.to_string_lossy().as_bytes())
+                .map_err(|_| format!("Invalid plugin path: {}", path.display()))?;
+            let handle = unsafe { dlopen(path_text.as_ptr(), RTLD_NOW) };
+            if handle.is_null() {
+                return Err(format!(
+                    "Couldn't load {}: {}",
+                    path.display(),
+                    get_dl_error()
+                ));
+            }
+            Ok(Library { handle })
+        }
+
+        fn get_symbol(&self, name: &str) -> Result<*mut c_void, String> {
+            let name_text = CString::new(name).expect("Symbol names have no NUL bytes");
+            let symbol = unsafe { dlsym(self.handle, name_text.as_ptr()) };
+            if symbol.is_null() {
+                return Err(format!("Missing symbol {}: {}", name, get_dl_error()));
+            }
+            Ok(symbol)
+        }
+    }
+
+    impl Drop for Library {
+        fn drop(&mut self) {
+            unsafe {
+                dlclose(self.handle);
+            }
+        }
+    }
+
+    fn get_dl_error() -> String {
+        let message = unsafe { dlerror() };
+        if message.is_null() {
+            return "unknown error".to_string();
+        }
+        unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .to_string()
+    }
+
+    struct Registration {
+        day: u8,
+        part: u8,
+        name: String,
+        solve: SolveFn,
+    }
+
+    unsafe extern "C" fn register(
+        context: *mut c_void,
+        day: u32,
+        part: u32,
+        name: *const c_char,
+        solve: SolveFn,
+    ) {
+        let registrations = &mut *(context as *mut Vec<Registration>);
+        if name.is_null() {
+            return;
+        }
+        let (Ok(day), Ok(part)) = (u8::try_from(day), u8::try_from(part)) else {
+            return;
+        };
+        registrations.push(Registration {
+            day,
+            part,
+            name: CStr::from_ptr(name).to_string_lossy().to_string(),
+            solve,
+        });
+    }
+
+    pub fn load_plugin(path: &Path) -> Result<Vec<PluginSolver>, String> {
+        let library = Rc::new(Library::open(path)?);
+        let plugin_register: PluginRegisterFn =
+            unsafe { std::mem::transmute(library.get_symbol(REGISTER_SYMBOL)?) };
+        let free: FreeFn = unsafe { std::mem::transmute(library.get_symbol(FREE_SYMBOL)?) };
+
+        let mut registrations: Vec<Registration> = Vec::new();
+        unsafe {
+            plugin_register(&mut registrations as *mut _ as *mut c_void, register);
+        }
+
+        Ok(registrations
+            .into_iter()
+            .map(|registration| PluginSolver {
+                name: registration.name,
+                day: registration.day,
+                part: registration.part,
+                path: path.to_path_buf(),
+                solve: registration.solve,
+                free,
+                _library: Rc::clone(&library),
+            })
+            .collect())
+    }
+}
+
+#[cfg(not(unix))]
+mod loader {
+    use std::path::Path;
+
+    use super::{PluginSolver, UNSUPPORTED_PLATFORM};
+
+    pub fn load_plugin(_path: &Path) -> Result<Vec<PluginSolver>, String> {
+        Err(UNSUPPORTED_PLATFORM.to_string())
+    }
+}
+
+fn is_plugin_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|extension| extension == DLL_EXTENSION)
+}
+
+fn get_files(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    files.sort();
+    files
+}
+
+pub fn get_default_plugin_directory() -> PathBuf {
+    solver::get_repository_directory().join("plugins")
+}
+
+// Libraries dropped straight into the directory are loaded, and so are the
+// release builds of plugin crates kept in its subdirectories. A library that
+// fails to load is skipped with a warning, so one stale build doesn't hide
+// the others.
+pub fn load_plugins(directory: &Path) -> Result<Vec<PluginSolver>, String> {
+    if cfg!(not(unix)) {
+        return Err(UNSUPPORTED_PLATFORM.to_string());
+    }
+    let mut plugin_paths = Vec::new();
+    for path in get_files(directory) {
+        if is_plugin_file(&path) {
+            plugin_paths.push(path);
+        } else if path.is_dir() {
+            let release_directory = path.join("target").join("release");
+            plugin_paths.extend(
+                get_files(&release_directory)
+                    .into_iter()
+                    .filter(|path| is_plugin_file(path)),
+            );
+        }
+    }
+
+    let mut solvers = Vec::new();
+    for path in plugin_paths {
+        match loader::load_plugin(&path) {
+            Ok(path_solvers) => solvers.extend(path_solvers),
+            Err(err) => eprintln!("Skipping a plugin library. {}", err),
+        }
+    }
+    Ok(solvers)
+}
+
+// Answers are timed the same way as the built-in days: from the start of the
+// run until the part's answer is known.
+pub fn run_plugin(
+    day: &Day,
+    name: &str,
+    input_path: Option<&Path>,
+    plugin_directory: &Path,
+) -> Result<Run, String> {
+    let mut solvers: Vec<PluginSolver> = load_plugins(plugin_directory)?
+        .into_iter()
+        .filter(|solver| solver.day == day.number && solver.name == name)
+        .collect();
+    if solvers.is_empty() {
+        return Err(format!(
+            "No plugin named {} solves {} in {}",
+            name,
+            day.name(),
+            plugin_directory.display()
+        ));
+    }
+    solvers.sort_by_key(|solver| solver.part);
+
+    let default_input_path = solver::get_day_directory(day).join("input.txt");
+    let input_path = input_path.unwrap_or(&default_input_path);
+    let input = fs::read_to_string(input_path)
+        .map_err(|err| format!("Couldn't read {}: {}", input_path.display(), err))?;
+
+    let before = Instant::now();
+    let mut answers = Vec::new();
+    for solver in &solvers {
+        answers.push(Answer {
+            part: solver.part,
+            value: solver.solve(&input)?,
+            elapsed: before.elapsed(),
+        });
+    }
+    Ok(Run {
+        answers,
+        elapsed: before.elapsed(),
+        stderr: String::new(),
+    })
+}
+
+// Plugins run inside the runner, so a plugin that never returns can only be
+// stopped by running it in a separate `runner solve` process. None when it
+// is still running after `timeout`.
+pub fn run_plugin_with_timeout(
+    day: &Day,
+    name: &str,
+    input_path: &Path,
+    plugin_directory: &Path,
+    timeout: Duration,
+) -> Result<Option<Run>, String> {
+    let runner = env::current_exe().map_err(|err| format!("Couldn't find the runner: {}", err))?;
+    let mut command = Command::new(runner);
+    command
+        .arg("solve")
+        .arg(day.number.to_string())
+        .arg(input_path)
+        .arg("--impl")
+        .arg(format!("plugin:{}", name))
+        .arg("--plugin-dir")
+        .arg(plugin_directory);
+
+    let Some(output) = solver::run_command_with_timeout(&mut command, timeout)? else {
+        return Ok(None);
+    };
+    if !output.status.success() {
+        return Err(output.stderr.trim().to_string());
+    }
+    // `runner solve` prints "dayN part P: answer" lines.
+    let prefix = format!("{} part ", day.name());
+    let answers = output
+        .lines
+        .iter()
+        .filter_map(|line| {
+            let (part, value) = line.text.strip_prefix(&prefix)?.split_once(": ")?;
+            Some(Answer {
+                part: part.parse().ok()?,
+                value: value.trim().to_string(),
+                elapsed: line.elapsed,
+            })
+        })
+        .collect();
+    Ok(Some(Run {
+        answers,
+        elapsed: output.elapsed,
+        stderr: output.stderr,
+    }))
+}
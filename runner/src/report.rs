@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+
+use crate::days::Day;
+use crate::history;
+use crate::solver::{self, Run};
+use crate::statistics;
+
+fn write_day_section(report: &mut String, day: &Day, input_path: &Path, run: &Result<Run, String>) {
+    report.push_str(&format!("\n## {}\n\n", day.name()));
+
+    match fs::read_to_string(input_path) {
+        Ok(text) => {
+            report.push_str("| Input statistic | Value |\n| --- | --- |\n");
+            for statistic in statistics::get_input_statistics(day, &text) {
+                report.push_str(&format!("| {} | {} |\n", statistic.label, statistic.value));
+            }
+        }
+        Err(err) => report.push_str(&format!(
+            "Couldn't read `{}`: {}\n",
+            input_path.display(),
+            err
+        )),
+    }
+
+    let run = match run {
+        Ok(run) => run,
+        Err(err) => {
+            report.push_str(&format!("\n**Failed:**\n\n```\n{}\n```\n", err.trim()));
+            return;
+        }
+    };
+
+    report.push_str("\n| Part | Answer | Time |\n| --- | --- | --- |\n");
+    for answer in &run.answers {
+        report.push_str(&format!(
+            "| {} | {} | {:.2?} |\n",
+            answer.part, answer.value, answer.elapsed
+        ));
+    }
+    report.push_str(&format!("\nTotal time: {:.2?}\n", run.elapsed));
+
+    let warnings = run.stderr.trim();
+    if warnings.is_empty() {
+        report.push_str("\nNo warnings.\n");
+    } else {
+        report.push_str(&format!("\n**Warnings:**\n\n```\n{}\n```\n", warnings));
+    }
+}
+
+pub fn create_report(days: &[&Day]) -> String {
+    let mut summary = String::from("| Day | Part | Answer | Time |\n| --- | --- | --- | --- |\n");
+    let mut sections = String::new();
+
+    for day in days {
+        let input_path = solver::get_day_directory(day).join("input.txt");
+        let run = solver::run_day(day, None);
+        match &run {
+            Ok(run) => {
+                for answer in &run.answers {
+                    summary.push_str(&format!(
+                        "| {} | {} | {} | {:.2?} |\n",
+                        day.name(),
+                        answer.part,
+                        answer.value,
+                        answer.elapsed
+                    ));
+                }
+            }
+            Err(_) => summary.push_str(&format!("| {} | | failed | |\n", day.name())),
+        }
+        write_day_section(&mut sections, day, &input_path, &run);
+    }
+
+    format!(
+        "# Solutions report\n\nGenerated at commit `{}`.\n\n{}{}",
+        history::get_git_commit(),
+        summary,
+        sections
+    )
+}
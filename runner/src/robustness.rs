@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::time::Duration;
+
+use crate::days::Day;
+use crate::random::Random;
+use crate::solver::{self, Output};
+
+const PANIC_EXIT_CODE: i32 = 101;
+const NON_ASCII_SNIPPETS: [&str; 6] = ["é", "ß", "€", "ção", "🎄", "\u{feff}"];
+const REPLACEMENT_CHARACTERS: &str = " .:,;|#?-0123456789abcLRSJF7()=";
+// A stray carriage return, a NUL, a lone continuation byte, a truncated
+// two-byte sequence and a byte that never appears in UTF-8.
+const RAW_BYTES: [u8; 5] = [b'\r', 0x00, 0x80, 0xc3, 0xff];
+
+// Text mutations work on decoded lines, byte mutations on the raw bytes.
+#[derive(Debug, Clone, Copy)]
+enum TextMutation {
+    Truncate,
+    DuplicateLine,
+    RemoveLine,
+    SwapLines,
+    SwapCharacters,
+    ReplaceCharacter,
+    InsertNonAscii,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ByteMutation {
+    FlipBit,
+    InsertByte,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mutation {
+    Text(TextMutation),
+    Byte(ByteMutation),
+}
+
+const MUTATIONS: [Mutation; 9] = [
+    Mutation::Text(TextMutation::Truncate),
+    Mutation::Text(TextMutation::DuplicateLine),
+    Mutation::Text(TextMutation::RemoveLine),
+    Mutation::Text(TextMutation::SwapLines),
+    Mutation::Text(TextMutation::SwapCharacters),
+    Mutation::Text(TextMutation::ReplaceCharacter),
+    Mutation::Text(TextMutation::InsertNonAscii),
+    Mutation::Byte(ByteMutation::FlipBit),
+    Mutation::Byte(ByteMutation::InsertByte),
+];
+
+#[derive(Debug)]
+pub enum Outcome {
+    Answered,
+    Rejected,
+    Crashed(String),
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub struct Crash {
+    pub message: String,
+    pub input_path: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct DayReport {
+    pub answered: usize,
+    pub rejected: usize,
+    pub timed_out: usize,
+    pub crashes: Vec<Crash>,
+}
+
+// Byte mutations run after the text ones, which work on decoded lines and
+// would otherwise replace the invalid UTF-8 they produce.
+pub fn mutate(text: &str, random: &mut Random) -> Vec<u8> {
+    let mutation_count = 1 + random.below(3);
+    let mut text_mutations = Vec::new();
+    let mut byte_mutations = Vec::new();
+    for _ in 0..mutation_count {
+        match *random.choose(&MUTATIONS) {
+            Mutation::Text(mutation) => text_mutations.push(mutation),
+            Mutation::Byte(mutation) => byte_mutations.push(mutation),
+        }
+    }
+
+    let mut result = text.to_string();
+    for mutation in text_mutations {
+        result = apply_mutation(&result, mutation, random);
+    }
+    let mut bytes = result.into_bytes();
+    for mutation in byte_mutations {
+        apply_byte_mutation(&mut bytes, mutation, random);
+    }
+    bytes
+}
+
+fn apply_byte_mutation(bytes: &mut Vec<u8>, mutation: ByteMutation, random: &mut Random) {
+    match mutation {
+        ByteMutation::FlipBit => {
+            if !bytes.is_empty() {
+                let position = random.below(bytes.len());
+                bytes[position] ^= 1 << random.below(8);
+            }
+        }
+        ByteMutation::InsertByte => {
+            let position = random.below(bytes.len() + 1);
+            bytes.insert(position, *random.choose(&RAW_BYTES));
+        }
+    }
+}
+
+fn apply_mutation(text: &str, mutation: TextMutation, random: &mut Random) -> String {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    if lines.is_empty() {
+        return text.to_string();
+    }
+    let i = random.below(lines.len());
+    let j = random.below(lines.len());
+    let mut line: Vec<char> = lines[i].chars().collect();
+
+    match mutation {
+        TextMutation::Truncate => {
+            let chars: Vec<char> = text.chars().collect();
+            return chars[..random.below(chars.len() + 1)].iter().collect();
+        }
+        TextMutation::DuplicateLine => lines.insert(i, lines[i].clone()),
+        TextMutation::RemoveLine => {
+            lines.remove(i);
+        }
+        TextMutation::SwapLines => lines.swap(i, j),
+        TextMutation::SwapCharacters => {
+            if line.len() > 1 {
+                let position = random.below(line.len() - 1);
+                line.swap(position, position + 1);
+            }
+            lines[i] = line.into_iter().collect();
+        }
+        TextMutation::ReplaceCharacter => {
+            if !line.is_empty() {
+                let position = random.below(line.len());
+                let replacements: Vec<char> = REPLACEMENT_CHARACTERS.chars().collect();
+                line[position] = *random.choose(&replacements);
+            }
+            lines[i] = line.into_iter().collect();
+        }
+        TextMutation::InsertNonAscii => {
+            let position = random.below(line.len() + 1);
+            let snippet = random.choose(&NON_ASCII_SNIPPETS);
+            line.splice(position..position, snippet.chars());
+            lines[i] = line.into_iter().collect();
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+fn classify_status(status: ExitStatus, stderr: &str) -> Outcome {
+    if status.success() {
+        return Outcome::Answered;
+    }
+    if let Some(panic_line) = stderr.lines().position(|line| line.contains("panicked at")) {
+        let message: Vec<&str> = stderr.lines().skip(panic_line).take(2).collect();
+        return Outcome::Crashed(message.join(" "));
+    }
+    match status.code() {
+        Some(PANIC_EXIT_CODE) => Outcome::Crashed("exited with the panic exit code".to_string()),
+        Some(_) => Outcome::Rejected,
+        None => Outcome::Crashed("killed by a signal".to_string()),
+    }
+}
+
+// Takes the result of solver::run_command_with_timeout, where None means
+// the run was killed.
+pub fn classify(output: Option<Output>) -> Outcome {
+    match output {
+        Some(output) => classify_status(output.status, &output.stderr),
+        None => Outcome::TimedOut,
+    }
+}
+
+pub fn check_day(
+    day: &Day,
+    iterations: usize,
+    timeout: Duration,
+    random: &mut Random,
+) -> Result<DayReport, String> {
+    let binary = solver::build_day(day)?;
+    let sample_input = fs::read_to_string(solver::get_sample_input_path(day))
+        .map_err(|err| format!("Couldn't read the {} sample input: {}", day.name(), err))?;
+
+    let crashes_directory = solver::get_repository_directory()
+        .join("runner")
+        .join("target")
+        .join("robustness");
+    fs::create_dir_all(&crashes_directory).map_err(|err| err.to_string())?;
+
+    let mut report = DayReport::default();
+    for iteration in 0..iterations {
+        let input_path = crashes_directory.join(format!("{}-{}.txt", day.name(), iteration));
+        fs::write(&input_path, mutate(&sample_input, random)).map_err(|err| err.to_string())?;
+
+        let mut command = solver::get_day_command(day, &binary, Some(&input_path))?;
+        match classify(solver::run_command_with_timeout(&mut command, timeout)?) {
+            Outcome::Answered => report.answered += 1,
+            Outcome::Rejected => report.rejected += 1,
+            Outcome::TimedOut => report.timed_out += 1,
+            Outcome::Crashed(message) => {
+                report.crashes.push(Crash {
+                    message,
+                    input_path,
+                });
+                continue;
+            }
+        }
+        let _ = fs::remove_file(&input_path);
+    }
+    Ok(report)
+}
@@ -0,0 +1,120 @@
+use std::fs;
+
+use crate::days;
+use crate::solver;
+
+const REGISTRY_END_MARKER: &str = "];\n\npub fn find_day";
+
+const CARGO_TOML_TEMPLATE: &str = r#"[package]
+name = "day{day}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+common = { path = "../common" }
+"#;
+
+const MAIN_TEMPLATE: &str = r#"use common::cli::Args;
+use common::input;
+
+const FILE_PATH: &str = "input.txt";
+// const FILE_PATH: &str = "smaller_input.txt";
+
+struct Solution {
+    lines: Vec<String>,
+}
+
+impl Solution {
+    fn from(lines: Vec<String>) -> Solution {
+        Solution { lines }
+    }
+
+    fn part_one(&self) -> u64 {
+        self.lines.iter().map(|_line| 0).sum()
+    }
+
+    fn part_two(&self) -> u64 {
+        self.lines.iter().map(|_line| 0).sum()
+    }
+}
+
+fn main() {
+    let args = Args::parse(&[]);
+    let lines = input::read_lines(&args.get_input_file_path(FILE_PATH));
+    let solution = Solution::from(lines);
+    println!("Part one solution: {}", solution.part_one());
+    println!("Part two solution: {}", solution.part_two());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALLER_INPUT_FILE_PATH: &str = "smaller_input.txt";
+
+    #[test]
+    fn part_one_matches_smaller_input() {
+        let solution = Solution::from(input::read_lines(SMALLER_INPUT_FILE_PATH));
+        assert_eq!(solution.part_one(), 0);
+    }
+
+    #[test]
+    fn part_two_matches_smaller_input() {
+        let solution = Solution::from(input::read_lines(SMALLER_INPUT_FILE_PATH));
+        assert_eq!(solution.part_two(), 0);
+    }
+}
+"#;
+
+const REGISTRY_ENTRY_TEMPLATE: &str = r#"    Day {
+        number: {day},
+        parts: &[
+            part(1, "Part one solution: "),
+            part(2, "Part two solution: "),
+        ],
+    },
+"#;
+
+pub fn create_day(number: u8) -> Result<(), String> {
+    if days::find_day(number).is_some() {
+        return Err(format!("day{} is already registered", number));
+    }
+
+    let repository_directory = solver::get_repository_directory();
+    let day_directory = repository_directory.join(format!("day{}", number));
+    if day_directory.exists() {
+        return Err(format!("{} already exists", day_directory.display()));
+    }
+
+    let registry_path = repository_directory
+        .join("runner")
+        .join("src")
+        .join("days.rs");
+    let registry = fs::read_to_string(&registry_path)
+        .map_err(|err| format!("Couldn't read {}: {}", registry_path.display(), err))?;
+    let registry_end = registry
+        .find(REGISTRY_END_MARKER)
+        .ok_or("Couldn't find the end of the day registry")?;
+
+    let fill_in = |template: &str| template.replace("{day}", &number.to_string());
+    let files = [
+        ("Cargo.toml", fill_in(CARGO_TOML_TEMPLATE)),
+        ("src/main.rs", fill_in(MAIN_TEMPLATE)),
+        ("input.txt", String::new()),
+        ("smaller_input.txt", String::new()),
+    ];
+    fs::create_dir_all(day_directory.join("src")).map_err(|err| err.to_string())?;
+    for (file_name, contents) in files {
+        let file_path = day_directory.join(file_name);
+        fs::write(&file_path, contents)
+            .map_err(|err| format!("Couldn't write {}: {}", file_path.display(), err))?;
+        println!("Created {}", file_path.display());
+    }
+
+    let mut new_registry = registry;
+    new_registry.insert_str(registry_end, &fill_in(REGISTRY_ENTRY_TEMPLATE));
+    fs::write(&registry_path, new_registry)
+        .map_err(|err| format!("Couldn't write {}: {}", registry_path.display(), err))?;
+    println!("Registered day{} in {}", number, registry_path.display());
+    Ok(())
+}
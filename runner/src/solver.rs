@@ -0,0 +1,296 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+use crate::plugins;
+
+// An answer's elapsed time runs from the start of the day until the
+// line carrying it was printed.
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub part: u8,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct TimedLine {
+    pub text: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub answers: Vec<Answer>,
+    pub elapsed: Duration,
+    // Whatever the day wrote to stderr, such as warnings from its parser.
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Implementation {
+    Builtin,
+    Plugin(String),
+}
+
+impl Implementation {
+    pub fn from(text: &str) -> Result<Implementation, String> {
+        match text {
+            "builtin" => Ok(Implementation::Builtin),
+            _ => match text.strip_prefix("plugin:") {
+                Some(name) if !name.is_empty() => Ok(Implementation::Plugin(name.to_string())),
+                _ => Err(format!("Unknown implementation: {}", text)),
+            },
+        }
+    }
+}
+
+pub fn get_repository_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("Runner should live inside the repository")
+        .to_path_buf()
+}
+
+pub fn get_day_directory(day: &Day) -> PathBuf {
+    get_repository_directory().join(day.name())
+}
+
+pub fn get_sample_input_path(day: &Day) -> PathBuf {
+    get_day_directory(day).join("smaller_input.txt")
+}
+
+pub fn build_day(day: &Day) -> Result<PathBuf, String> {
+    let day_directory = get_day_directory(day);
+    let output = Command::new("cargo")
+        .args(["build", "--release", "--quiet"])
+        .current_dir(&day_directory)
+        .output()
+        .map_err(|err| format!("Couldn't run cargo for {}: {}", day.name(), err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Couldn't build {}: {}",
+            day.name(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(day_directory
+        .join("target")
+        .join("release")
+        .join(day.name()))
+}
+
+// Runs from the day's directory, so it finds its default input there.
+pub fn get_day_command(
+    day: &Day,
+    binary: &Path,
+    input_path: Option<&Path>,
+) -> Result<Command, String> {
+    let mut command = Command::new(binary);
+    command.current_dir(get_day_directory(day));
+    if let Some(input_path) = input_path {
+        let input_path = input_path
+            .canonicalize()
+            .map_err(|err| format!("Couldn't find {}: {}", input_path.display(), err))?;
+        command.arg(input_path);
+    }
+    Ok(command)
+}
+
+// Reads stderr on its own thread, so a chatty child can't fill the pipe
+// and block while stdout is being read.
+fn spawn_stderr_reader(child: &mut Child) -> JoinHandle<String> {
+    let mut stderr_pipe = child.stderr.take().expect("Stderr should be piped");
+    thread::spawn(move || {
+        let mut stderr = String::new();
+        let _ = stderr_pipe.read_to_string(&mut stderr);
+        stderr
+    })
+}
+
+pub fn run_day(day: &Day, input_path: Option<&Path>) -> Result<Run, String> {
+    let binary = build_day(day)?;
+    run_built_day(day, &binary, input_path, |_| {})
+}
+
+// `on_line` sees every output line as soon as the day prints it.
+pub fn run_built_day(
+    day: &Day,
+    binary: &Path,
+    input_path: Option<&Path>,
+    on_line: impl FnMut(&TimedLine),
+) -> Result<Run, String> {
+    let mut command = get_day_command(day, binary, input_path)?;
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let before = Instant::now();
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Couldn't run {}: {}", day.name(), err))?;
+
+    let stderr_reader = spawn_stderr_reader(&mut child);
+    let stdout_pipe = child.stdout.take().expect("Stdout should be piped");
+    let lines = read_timed_lines(stdout_pipe, before, on_line);
+
+    let status = child.wait().map_err(|err| err.to_string())?;
+    let elapsed = before.elapsed();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(format!("{} failed: {}", day.name(), stderr.trim()));
+    }
+
+    Ok(Run {
+        answers: extract_answers(day, &lines),
+        elapsed,
+        stderr,
+    })
+}
+
+#[derive(Debug)]
+pub struct Output {
+    pub status: ExitStatus,
+    pub lines: Vec<TimedLine>,
+    pub stderr: String,
+    pub elapsed: Duration,
+}
+
+// The one place a child process is run under a timeout. Kills the command
+// once `timeout` has passed, and then returns None.
+pub fn run_command_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+) -> Result<Option<Output>, String> {
+    let before = Instant::now();
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Couldn't run {:?}: {}", command.get_program(), err))?;
+
+    let stderr_reader = spawn_stderr_reader(&mut child);
+    let stdout_pipe = child.stdout.take().expect("Stdout should be piped");
+    let stdout_reader = thread::spawn(move || read_timed_lines(stdout_pipe, before, |_| {}));
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|err| err.to_string())? {
+            break Some(status);
+        }
+        if before.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(1));
+    };
+
+    let elapsed = before.elapsed();
+    let lines = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    Ok(status.map(|status| Output {
+        status,
+        lines,
+        stderr,
+        elapsed,
+    }))
+}
+
+// None when the day is still running after `timeout`.
+pub fn run_built_day_with_timeout(
+    day: &Day,
+    binary: &Path,
+    input_path: &Path,
+    timeout: Duration,
+) -> Result<Option<Run>, String> {
+    let mut command = get_day_command(day, binary, Some(input_path))?;
+    let Some(output) = run_command_with_timeout(&mut command, timeout)? else {
+        return Ok(None);
+    };
+    if !output.status.success() {
+        return Err(format!("{} failed: {}", day.name(), output.stderr.trim()));
+    }
+    Ok(Some(Run {
+        answers: extract_answers(day, &output.lines),
+        elapsed: output.elapsed,
+        stderr: output.stderr,
+    }))
+}
+
+pub fn run_implementation(
+    day: &Day,
+    implementation: &Implementation,
+    input_path: Option<&Path>,
+    plugin_directory: &Path,
+) -> Result<Run, String> {
+    match implementation {
+        Implementation::Builtin => run_day(day, input_path),
+        Implementation::Plugin(name) => {
+            plugins::run_plugin(day, name, input_path, plugin_directory)
+        }
+    }
+}
+
+// Days print progress with '\r', so both line endings split the output.
+fn read_timed_lines(
+    mut reader: impl Read,
+    before: Instant,
+    mut on_line: impl FnMut(&TimedLine),
+) -> Vec<TimedLine> {
+    let mut lines = Vec::new();
+    let mut line = Vec::new();
+    let mut buffer = [0; 4096];
+    while let Ok(count) = reader.read(&mut buffer) {
+        if count == 0 {
+            break;
+        }
+        for &byte in &buffer[..count] {
+            if byte == b'\n' || byte == b'\r' {
+                let timed_line = TimedLine {
+                    text: String::from_utf8_lossy(&line).to_string(),
+                    elapsed: before.elapsed(),
+                };
+                on_line(&timed_line);
+                lines.push(timed_line);
+                line.clear();
+            } else {
+                line.push(byte);
+            }
+        }
+    }
+    if !line.is_empty() {
+        let timed_line = TimedLine {
+            text: String::from_utf8_lossy(&line).to_string(),
+            elapsed: before.elapsed(),
+        };
+        on_line(&timed_line);
+        lines.push(timed_line);
+    }
+    lines
+}
+
+pub fn extract_answers(day: &Day, lines: &[TimedLine]) -> Vec<Answer> {
+    let lines: Vec<&TimedLine> = lines
+        .iter()
+        .filter(|line| !line.text.trim().is_empty())
+        .collect();
+
+    let mut used_lines = vec![false; lines.len()];
+    let mut answers = Vec::new();
+    for part in day.parts {
+        let line = lines
+            .iter()
+            .enumerate()
+            .find(|(i, line)| !used_lines[*i] && line.text.starts_with(part.answer_prefix));
+        if let Some((i, line)) = line {
+            used_lines[i] = true;
+            answers.push(Answer {
+                part: part.number,
+                value: line.text[part.answer_prefix.len()..].trim().to_string(),
+                elapsed: line.elapsed,
+            });
+        }
+    }
+    answers
+}
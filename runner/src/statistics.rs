@@ -0,0 +1,85 @@
+use crate::days::Day;
+
+#[derive(Debug)]
+pub struct Statistic {
+    pub label: &'static str,
+    pub value: String,
+}
+
+fn statistic(label: &'static str, value: impl ToString) -> Statistic {
+    Statistic {
+        label,
+        value: value.to_string(),
+    }
+}
+
+fn get_grid_size(lines: &[&str]) -> Option<(usize, usize)> {
+    let width = lines.first()?.chars().count();
+    lines
+        .iter()
+        .all(|line| line.chars().count() == width)
+        .then_some((width, lines.len()))
+}
+
+fn count_numbers_after(lines: &[&str], label: &str) -> usize {
+    lines
+        .iter()
+        .find_map(|line| line.strip_prefix(label))
+        .map(|numbers| numbers.split_whitespace().count())
+        .unwrap_or(0)
+}
+
+fn count_lines_starting_with(lines: &[&str], prefix: &str) -> usize {
+    lines.iter().filter(|line| line.starts_with(prefix)).count()
+}
+
+pub fn get_input_statistics(day: &Day, text: &str) -> Vec<Statistic> {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let mut statistics = vec![statistic("Lines", lines.len())];
+
+    match day.number {
+        1 => statistics.push(statistic("Calibration lines", lines.len())),
+        2 => statistics.push(statistic(
+            "Games",
+            count_lines_starting_with(&lines, "Game "),
+        )),
+        3 | 10 | 11 => {
+            if let Some((width, height)) = get_grid_size(&lines) {
+                statistics.push(statistic("Grid size", format!("{}x{}", width, height)));
+            }
+            if day.number == 11 {
+                statistics.push(statistic("Galaxies", text.matches('#').count()));
+            }
+        }
+        4 => statistics.push(statistic(
+            "Cards",
+            count_lines_starting_with(&lines, "Card "),
+        )),
+        5 => {
+            statistics.push(statistic("Seeds", count_numbers_after(&lines, "seeds:")));
+            statistics.push(statistic(
+                "Maps",
+                lines.iter().filter(|line| line.ends_with("map:")).count(),
+            ));
+        }
+        6 => statistics.push(statistic("Races", count_numbers_after(&lines, "Time:"))),
+        7 => statistics.push(statistic("Hands", lines.len())),
+        8 => {
+            statistics.push(statistic(
+                "Instructions",
+                lines.first().map(|line| line.len()).unwrap_or(0),
+            ));
+            statistics.push(statistic(
+                "Nodes",
+                lines.iter().filter(|line| line.contains(" = ")).count(),
+            ));
+        }
+        9 => statistics.push(statistic("Histories", lines.len())),
+        12 => statistics.push(statistic("Records", lines.len())),
+        _ => {}
+    }
+    statistics
+}